@@ -1,5 +1,16 @@
 // Copyright 2024 Bewusstsein Labs
 
+pub mod centrality;
+pub mod components;
+pub mod topological;
+pub mod transitive;
+pub mod cycles;
+pub mod dominators;
+pub mod euler;
+pub mod flow;
+pub mod min_cost_flow;
+pub mod tree;
+
 //: Standard
 use std::fmt::Display;
 
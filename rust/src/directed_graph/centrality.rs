@@ -0,0 +1,402 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::{ Ordering, Reverse },
+    collections::{ BTreeMap, BinaryHeap, VecDeque }
+};
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed,
+        weight::Weight
+    },
+    directed_graph::DiGraph
+};
+
+// Upper bound for the power iterations, they stop early once the tolerance is met.
+const MAX_ITERATIONS: usize = 1000;
+
+struct Brandes {
+    nodes: Vec<f64>,
+    edges: Vec<BTreeMap<usize, f64>>
+}
+
+// Brandes' accumulation over Dijkstra, shortest paths of equal length are all counted.
+fn brandes<W>( adjacency: &[Vec<( usize, W )>] ) -> Brandes
+where
+    W: Weight
+{
+    let n = adjacency.len();
+    let mut result = Brandes { nodes: vec![ 0.0; n ], edges: vec![ BTreeMap::new(); n ] };
+    for source in 0..n {
+        let mut distances: Vec<Option<W>> = vec![ None; n ];
+        let mut paths = vec![ 0.0; n ];
+        let mut predecessors: Vec<Vec<usize>> = vec![ Vec::new(); n ];
+        let mut done = vec![ false; n ];
+        let mut order = Vec::new();
+        let mut heap = BinaryHeap::new();
+        distances[ source ] = Some( W::zero() );
+        paths[ source ] = 1.0;
+        heap.push( Reverse( ( W::zero(), source ) ) );
+
+        while let Some( Reverse( ( distance, v ) ) ) = heap.pop() {
+            if std::mem::replace( &mut done[ v ], true ) {
+                continue;
+            }
+            order.push( v );
+            for ( w, weight ) in adjacency[ v ].iter() {
+                let ( w, next ) = ( *w, distance.clone() + weight.clone() );
+                if w == v || done[ w ] {
+                    continue;
+                }
+                match distances[ w ].as_ref().map( |best| next.cmp( best ) ) {
+                    None | Some( Ordering::Less ) => {
+                        distances[ w ] = Some( next.clone() );
+                        paths[ w ] = paths[ v ];
+                        predecessors[ w ] = vec![ v ];
+                        heap.push( Reverse( ( next, w ) ) );
+                    },
+                    Some( Ordering::Equal ) => {
+                        paths[ w ] += paths[ v ];
+                        predecessors[ w ].push( v );
+                    },
+                    Some( Ordering::Greater ) => {}
+                }
+            }
+        }
+
+        let mut dependency = vec![ 0.0; n ];
+        while let Some( w ) = order.pop() {
+            for &v in predecessors[ w ].iter() {
+                let share = paths[ v ] / paths[ w ] * ( 1.0 + dependency[ w ] );
+                *result.edges[ v ].entry( w ).or_insert( 0.0 ) += share;
+                dependency[ v ] += share;
+            }
+            if w != source {
+                result.nodes[ w ] += dependency[ w ];
+            }
+        }
+    }
+    result
+}
+
+// Hop distances from every node to `target`, following edges backwards.
+fn distances_to( incoming: &[Vec<usize>], target: usize ) -> Vec<Option<usize>> {
+    let mut distances = vec![ None; incoming.len() ];
+    let mut queue = VecDeque::from( [ target ] );
+    distances[ target ] = Some( 0 );
+    while let Some( v ) = queue.pop_front() {
+        let next = distances[ v ].map_or( 0, |distance| distance + 1 );
+        for &u in incoming[ v ].iter() {
+            if distances[ u ].is_none() {
+                distances[ u ] = Some( next );
+                queue.push_back( u );
+            }
+        }
+    }
+    distances
+}
+
+fn incoming( adjacency: &[Vec<usize>] ) -> Vec<Vec<usize>> {
+    let mut incoming = vec![ Vec::new(); adjacency.len() ];
+    for ( u, edges ) in adjacency.iter().enumerate() {
+        edges.iter().for_each( |&v| incoming[ v ].push( u ) );
+    }
+    incoming
+}
+
+fn difference( a: &[f64], b: &[f64] ) -> f64 {
+    a.iter().zip( b.iter() ).map( |( a, b )| ( a - b ).abs() ).sum()
+}
+
+fn normalize( values: &mut [f64] ) {
+    let norm = values.iter().map( |value| value * value ).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        values.iter_mut().for_each( |value| *value /= norm );
+    }
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn scores( indexed: &Indexed<'_, I, E>, values: Vec<f64> ) -> BTreeMap<I, f64> {
+        indexed.ids.iter().cloned().zip( values ).collect()
+    }
+
+    // Power iteration until the summed change drops below `tolerance`. Random jumps and dangling nodes
+    // follow `personalization`, uniform when none is given or its weights sum to zero.
+    pub fn pagerank( &self, damping: f64, personalization: Option<&BTreeMap<I, f64>>, tolerance: f64 ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        if n == 0 {
+            return BTreeMap::new();
+        }
+
+        let mut jump: Vec<f64> = indexed.ids.iter()
+            .map( |id| personalization.map_or( 1.0, |weights| weights.get( id ).copied().unwrap_or( 0.0 ) ) )
+            .collect();
+        let total: f64 = jump.iter().sum();
+        if total > 0.0 {
+            jump.iter_mut().for_each( |weight| *weight /= total );
+        } else {
+            jump.iter_mut().for_each( |weight| *weight = 1.0 / n as f64 );
+        }
+
+        let mut ranks = jump.clone();
+        for _ in 0..MAX_ITERATIONS {
+            let dangling: f64 = ( 0..n ).filter( |&v| adjacency[ v ].is_empty() ).map( |v| ranks[ v ] ).sum();
+            let mut next: Vec<f64> = jump.iter().map( |weight| weight * ( 1.0 - damping + damping * dangling ) ).collect();
+            for ( u, edges ) in adjacency.iter().enumerate() {
+                let share = damping * ranks[ u ] / edges.len() as f64;
+                edges.iter().for_each( |&v| next[ v ] += share );
+            }
+            let change = difference( &next, &ranks );
+            ranks = next;
+            if change < tolerance {
+                break;
+            }
+        }
+        Self::scores( &indexed, ranks )
+    }
+
+    pub fn betweenness_centrality( &self, normalized: bool ) -> BTreeMap<I, f64> {
+        self.betweenness_centrality_by( |_| 1usize, normalized )
+    }
+
+    // Fraction of shortest paths between other pairs running through each node. Normalized scores are
+    // divided by the ( n - 1 )( n - 2 ) ordered pairs.
+    pub fn betweenness_centrality_by<W, F>( &self, weight: F, normalized: bool ) -> BTreeMap<I, f64>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self.data() );
+        let n = indexed.ids.len() as f64;
+        let scale = if normalized && n > 2.0 { 1.0 / ( ( n - 1.0 ) * ( n - 2.0 ) ) } else { 1.0 };
+        let nodes = brandes( &indexed.weighted( weight ) ).nodes.into_iter().map( |value| value * scale ).collect();
+        Self::scores( &indexed, nodes )
+    }
+
+    pub fn edge_betweenness_centrality( &self, normalized: bool ) -> BTreeMap<( I, I ), f64> {
+        self.edge_betweenness_centrality_by( |_| 1usize, normalized )
+    }
+
+    // Same as `betweenness_centrality_by` for edges, normalized over the n( n - 1 ) ordered pairs.
+    pub fn edge_betweenness_centrality_by<W, F>( &self, weight: F, normalized: bool ) -> BTreeMap<( I, I ), f64>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self.data() );
+        let n = indexed.ids.len() as f64;
+        let scale = if normalized && n > 1.0 { 1.0 / ( n * ( n - 1.0 ) ) } else { 1.0 };
+        let edges = brandes( &indexed.weighted( weight ) ).edges;
+        indexed.adjacency.iter().enumerate()
+            .flat_map( |( u, targets )| targets.iter().filter( move |&&( v, _ )| u != v ).map( move |&( v, _ )| ( u, v ) ) )
+            .map( |( u, v )| (
+                ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() ),
+                edges[ u ].get( &v ).copied().unwrap_or( 0.0 ) * scale
+            ) )
+            .collect()
+    }
+
+    // Inverse average hop distance from the nodes that can reach each node, scaled by the share of
+    // nodes that can reach it at all ( Wasserman and Faust ).
+    pub fn closeness_centrality( &self ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let incoming = incoming( &indexed.neighbors() );
+        let n = incoming.len();
+        let closeness = ( 0..n )
+            .map( |v| {
+                let distances = distances_to( &incoming, v );
+                let reached = distances.iter().flatten().filter( |&&distance| distance > 0 ).count() as f64;
+                let total = distances.iter().flatten().sum::<usize>() as f64;
+                if total > 0.0 { ( reached / total ) * ( reached / ( n - 1 ) as f64 ) } else { 0.0 }
+            } )
+            .collect();
+        Self::scores( &indexed, closeness )
+    }
+
+    // Sum of inverse hop distances from every other node, unreachable nodes add nothing.
+    pub fn harmonic_centrality( &self ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let incoming = incoming( &indexed.neighbors() );
+        let harmonic = ( 0..incoming.len() )
+            .map( |v| distances_to( &incoming, v ).into_iter().flatten()
+                .filter( |&distance| distance > 0 )
+                .map( |distance| 1.0 / distance as f64 )
+                .sum()
+            )
+            .collect();
+        Self::scores( &indexed, harmonic )
+    }
+
+    fn degrees( &self, incoming: bool, outgoing: bool ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        let scale = if n > 1 { 1.0 / ( n - 1 ) as f64 } else { 1.0 };
+        let mut degrees = vec![ 0.0; n ];
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            for &v in edges.iter() {
+                if outgoing {
+                    degrees[ u ] += scale;
+                }
+                if incoming {
+                    degrees[ v ] += scale;
+                }
+            }
+        }
+        Self::scores( &indexed, degrees )
+    }
+
+    // Degrees divided by the n - 1 possible neighbours.
+    pub fn degree_centrality( &self ) -> BTreeMap<I, f64> {
+        self.degrees( true, true )
+    }
+
+    pub fn in_degree_centrality( &self ) -> BTreeMap<I, f64> {
+        self.degrees( true, false )
+    }
+
+    pub fn out_degree_centrality( &self ) -> BTreeMap<I, f64> {
+        self.degrees( false, true )
+    }
+
+    // Power iteration on incoming edges, shifted by the identity so it also settles on periodic graphs.
+    // Scores have unit euclidean norm.
+    pub fn eigenvector_centrality( &self, tolerance: f64 ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        let mut scores = vec![ 1.0 / n as f64; n ];
+        for _ in 0..MAX_ITERATIONS {
+            let mut next = scores.clone();
+            for ( u, edges ) in adjacency.iter().enumerate() {
+                edges.iter().for_each( |&v| next[ v ] += scores[ u ] );
+            }
+            normalize( &mut next );
+            let change = difference( &next, &scores );
+            scores = next;
+            if change < tolerance {
+                break;
+            }
+        }
+        Self::scores( &indexed, scores )
+    }
+
+    // Solves x = alpha * A^T x + beta by iteration, which only converges for alpha below the inverse of
+    // the largest eigenvalue. Scores have unit euclidean norm.
+    pub fn katz_centrality( &self, alpha: f64, beta: f64, tolerance: f64 ) -> BTreeMap<I, f64> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        let mut scores = vec![ 0.0; n ];
+        for _ in 0..MAX_ITERATIONS {
+            let mut next = vec![ beta; n ];
+            for ( u, edges ) in adjacency.iter().enumerate() {
+                edges.iter().for_each( |&v| next[ v ] += alpha * scores[ u ] );
+            }
+            let change = difference( &next, &scores );
+            scores = next;
+            if change < tolerance {
+                break;
+            }
+        }
+        normalize( &mut scores );
+        Self::scores( &indexed, scores )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::graph::fixtures::{ digraph, weighted_digraph };
+
+    fn assert_close( actual: f64, expected: f64 ) {
+        assert!( ( actual - expected ).abs() < 1e-6, "{} != {}", actual, expected );
+    }
+
+    #[test]
+    fn test_pagerank() {
+        // Cycle, every node gets the same rank.
+        let cycle = digraph( 4, &[ ( 0, 1 ), ( 1, 2 ), ( 2, 3 ), ( 3, 0 ) ] );
+        let ranks = cycle.pagerank( 0.85, None, 1e-10 );
+        ranks.values().for_each( |&rank| assert_close( rank, 0.25 ) );
+
+        // Star pointing inwards, the dangling hub collects the most.
+        let star = digraph( 4, &[ ( 1, 0 ), ( 2, 0 ), ( 3, 0 ) ] );
+        let ranks = star.pagerank( 0.85, None, 1e-10 );
+        assert_close( ranks.values().sum(), 1.0 );
+        assert!( ranks[ &0 ] > ranks[ &1 ] );
+        assert_close( ranks[ &1 ], ranks[ &2 ] );
+
+        let personalization = BTreeMap::from( [ ( 1, 1.0 ) ] );
+        let ranks = star.pagerank( 0.85, Some( &personalization ), 1e-10 );
+        assert_close( ranks[ &2 ], 0.0 );
+        assert!( ranks[ &1 ] > 0.0 );
+    }
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // Path 0 -> 1 -> 2 -> 3.
+        let path = digraph( 4, &[ ( 0, 1 ), ( 1, 2 ), ( 2, 3 ) ] );
+        let betweenness = path.betweenness_centrality( false );
+        assert_eq!( betweenness, BTreeMap::from( [ ( 0, 0.0 ), ( 1, 2.0 ), ( 2, 2.0 ), ( 3, 0.0 ) ] ) );
+        assert_close( path.betweenness_centrality( true )[ &1 ], 2.0 / 6.0 );
+
+        let edges = path.edge_betweenness_centrality( false );
+        assert_eq!( edges, BTreeMap::from( [ ( ( 0, 1 ), 3.0 ), ( ( 1, 2 ), 4.0 ), ( ( 2, 3 ), 3.0 ) ] ) );
+
+        // Diamond 0 -> { 1, 2 } -> 3, both middle nodes carry half of the 0 -> 3 path.
+        let diamond = weighted_digraph( 4, &[ ( 0, 1, 1 ), ( 0, 2, 5 ), ( 1, 3, 1 ), ( 2, 3, 1 ) ] );
+        assert_close( diamond.betweenness_centrality( false )[ &1 ], 0.5 );
+        let weighted = diamond.betweenness_centrality_by( |weight| *weight, false );
+        assert_close( weighted[ &1 ], 1.0 );
+        assert_close( weighted[ &2 ], 0.0 );
+    }
+
+    #[test]
+    fn test_closeness_centrality() {
+        let path = digraph( 3, &[ ( 0, 1 ), ( 1, 2 ) ] );
+        let closeness = path.closeness_centrality();
+        assert_close( closeness[ &0 ], 0.0 );
+        assert_close( closeness[ &1 ], 0.5 );
+        assert_close( closeness[ &2 ], 2.0 / 3.0 );
+
+        let harmonic = path.harmonic_centrality();
+        assert_close( harmonic[ &0 ], 0.0 );
+        assert_close( harmonic[ &1 ], 1.0 );
+        assert_close( harmonic[ &2 ], 1.5 );
+    }
+
+    #[test]
+    fn test_degree_centrality() {
+        let star = digraph( 4, &[ ( 1, 0 ), ( 2, 0 ), ( 3, 0 ), ( 0, 1 ) ] );
+        assert_close( star.in_degree_centrality()[ &0 ], 1.0 );
+        assert_close( star.out_degree_centrality()[ &0 ], 1.0 / 3.0 );
+        assert_close( star.degree_centrality()[ &0 ], 4.0 / 3.0 );
+        assert_close( star.degree_centrality()[ &2 ], 1.0 / 3.0 );
+    }
+
+    #[test]
+    fn test_eigenvector_centrality() {
+        // Complete graph on three nodes plus a node that only points into it.
+        let graph = digraph( 4, &[ ( 0, 1 ), ( 1, 0 ), ( 1, 2 ), ( 2, 1 ), ( 0, 2 ), ( 2, 0 ), ( 3, 0 ) ] );
+        let eigenvector = graph.eigenvector_centrality( 1e-12 );
+        assert_close( eigenvector[ &3 ], 0.0 );
+        assert_close( eigenvector[ &0 ], 1.0 / 3f64.sqrt() );
+        assert_close( eigenvector[ &1 ], eigenvector[ &2 ] );
+        assert_close( eigenvector.values().map( |value| value * value ).sum(), 1.0 );
+
+        let katz = graph.katz_centrality( 0.1, 1.0, 1e-12 );
+        assert!( katz[ &0 ] > katz[ &1 ] );
+        assert!( katz[ &3 ] > 0.0 );
+        assert_close( katz[ &1 ], katz[ &2 ] );
+    }
+}
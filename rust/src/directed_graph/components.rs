@@ -0,0 +1,163 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::BTreeMap;
+
+use crate::{
+    graph::{
+        GraphAccess,
+        GraphTraits,
+        indexed::Indexed
+    },
+    directed_graph::DiGraph
+};
+
+// Iterative Tarjan, components are emitted sinks first (reverse topological order of the condensation).
+pub(crate) fn tarjan( adjacency: &[Vec<usize>] ) -> Vec<Vec<usize>> {
+    let n = adjacency.len();
+    let mut counter = 0;
+    let mut index = vec![ usize::MAX; n ];
+    let mut low = vec![ 0; n ];
+    let mut on_stack = vec![ false; n ];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    for root in 0..n {
+        if index[ root ] != usize::MAX {
+            continue;
+        }
+        index[ root ] = counter;
+        low[ root ] = counter;
+        counter += 1;
+        stack.push( root );
+        on_stack[ root ] = true;
+        let mut calls = vec![ ( root, 0 ) ];
+
+        while let Some( frame ) = calls.last_mut() {
+            let v = frame.0;
+            if let Some( &w ) = adjacency[ v ].get( frame.1 ) {
+                frame.1 += 1;
+                if index[ w ] == usize::MAX {
+                    index[ w ] = counter;
+                    low[ w ] = counter;
+                    counter += 1;
+                    stack.push( w );
+                    on_stack[ w ] = true;
+                    calls.push( ( w, 0 ) );
+                } else if on_stack[ w ] {
+                    low[ v ] = low[ v ].min( index[ w ] );
+                }
+            } else {
+                calls.pop();
+                if let Some( &( u, _ ) ) = calls.last() {
+                    low[ u ] = low[ u ].min( low[ v ] );
+                }
+                if low[ v ] == index[ v ] {
+                    let mut component = Vec::new();
+                    while let Some( w ) = stack.pop() {
+                        on_stack[ w ] = false;
+                        component.push( w );
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push( component );
+                }
+            }
+        }
+    }
+    components
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    pub fn strongly_connected_components( &self ) -> Vec<Vec<I>> {
+        let indexed = Indexed::new( self.data() );
+        tarjan( &indexed.neighbors() ).into_iter()
+            .rev()
+            .map( |component| component.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() )
+            .collect()
+    }
+
+    pub fn condensation( &self ) -> ( DiGraph<usize, Vec<I>, ()>, BTreeMap<I, usize> ) {
+        let components = self.strongly_connected_components();
+        let mapping: BTreeMap<I, usize> = components.iter()
+            .enumerate()
+            .flat_map( |( component, ids )| ids.iter().map( move |id| ( id.clone(), component ) ) )
+            .collect();
+
+        let mut graph = DiGraph::new();
+        for ( component, ids ) in components.into_iter().enumerate() {
+            let _ = graph.add_node( component, ids );
+        }
+        for ( id, node ) in self.data().iter() {
+            let from = mapping[ id ];
+            for next_id in node.adjacencies().keys() {
+                if let Some( &to ) = mapping.get( next_id ) {
+                    if from != to && !graph.contains_edge( from, to ) {
+                        let _ = graph.add_edge( from, to, () );
+                    }
+                }
+            }
+        }
+        ( graph, mapping )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::GraphTraits,
+        directed_graph::DiGraph
+    };
+
+    fn graph() -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=7 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, () ).unwrap();
+        graph.add_edge( 2, 3, () ).unwrap();
+        graph.add_edge( 3, 1, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+        graph.add_edge( 4, 5, () ).unwrap();
+        graph.add_edge( 5, 4, () ).unwrap();
+        graph.add_edge( 6, 5, () ).unwrap();
+        graph.add_edge( 5, 7, () ).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let components = graph().strongly_connected_components();
+        assert_eq!( components.len(), 4 );
+        let position = |id: usize| components.iter().position( |c| c.contains( &id ) ).unwrap();
+        assert_eq!( components[ position( 1 ) ], vec![ 1, 2, 3 ] );
+        assert_eq!( components[ position( 4 ) ], vec![ 4, 5 ] );
+        assert!( position( 1 ) < position( 4 ) );
+        assert!( position( 6 ) < position( 4 ) );
+        assert!( position( 4 ) < position( 7 ) );
+    }
+
+    #[test]
+    fn test_strongly_connected_components_empty() {
+        let graph = DiGraph::<usize, (), ()>::new();
+        assert!( graph.strongly_connected_components().is_empty() );
+    }
+
+    #[test]
+    fn test_condensation() {
+        let ( condensed, mapping ) = graph().condensation();
+        assert_eq!( condensed.order(), 4 );
+        assert_eq!( condensed.size(), 3 );
+        assert_eq!( mapping[ &1 ], mapping[ &3 ] );
+        assert_eq!( mapping[ &4 ], mapping[ &5 ] );
+        assert!( condensed.contains_edge( mapping[ &3 ], mapping[ &4 ] ) );
+        assert!( condensed.contains_edge( mapping[ &6 ], mapping[ &5 ] ) );
+        assert_eq!( condensed.get_node( mapping[ &2 ] ), Some( &vec![ 1, 2, 3 ] ) );
+        assert!( condensed.strongly_connected_components().iter().all( |c| c.len() == 1 ) );
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        Cycle,
+        GraphAccess,
+        indexed::Indexed,
+        metrics::Distance,
+        weight::Weight
+    },
+    directed_graph::{
+        DiGraph,
+        components::tarjan
+    }
+};
+
+// Johnson's algorithm driven by an explicit stack so cycles can be handed out one at a time.
+// Every cycle is reported once, starting at its smallest node.
+#[derive( Debug, Clone )]
+pub struct ElementaryCycles<I> {
+    ids: Vec<I>,
+    adjacency: Vec<Vec<usize>>,
+    max_length: Option<usize>,
+    next_start: usize,
+    start: usize,
+    component: Vec<Vec<usize>>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    path: Vec<( usize, usize, bool )>
+}
+
+impl<I> ElementaryCycles<I>
+where
+    I: Clone
+{
+    fn new( ids: Vec<I>, adjacency: Vec<Vec<usize>>, max_length: Option<usize> ) -> Self {
+        let n = ids.len();
+        Self {
+            ids,
+            adjacency,
+            max_length,
+            next_start: 0,
+            start: 0,
+            component: vec![ Vec::new(); n ],
+            blocked: vec![ false; n ],
+            blocked_by: vec![ Vec::new(); n ],
+            path: Vec::new()
+        }
+    }
+
+    // Restricts the search to the strongly connected component of the next start node within
+    // the subgraph induced by the nodes not smaller than it.
+    fn advance( &mut self ) -> bool {
+        if self.max_length == Some( 0 ) {
+            return false;
+        }
+        while self.next_start < self.ids.len() {
+            let start = self.next_start;
+            self.next_start += 1;
+
+            let induced: Vec<Vec<usize>> = self.adjacency.iter()
+                .enumerate()
+                .map( |( v, edges )| if v < start {
+                    Vec::new()
+                } else {
+                    edges.iter().copied().filter( |&w| w >= start ).collect()
+                })
+                .collect();
+            let Some( members ) = tarjan( &induced ).into_iter().find( |component| component.contains( &start ) ) else {
+                continue;
+            };
+
+            self.component.iter_mut().for_each( Vec::clear );
+            for &v in members.iter() {
+                self.component[ v ] = induced[ v ].iter().copied().filter( |w| members.binary_search( w ).is_ok() ).collect();
+            }
+            if self.component[ start ].is_empty() {
+                continue;
+            }
+
+            self.start = start;
+            self.blocked.iter_mut().for_each( |blocked| *blocked = false );
+            self.blocked_by.iter_mut().for_each( Vec::clear );
+            self.blocked[ start ] = true;
+            self.path.push( ( start, 0, false ) );
+            return true;
+        }
+        false
+    }
+
+    fn unblock( &mut self, node: usize ) {
+        let mut stack = vec![ node ];
+        while let Some( v ) = stack.pop() {
+            if self.blocked[ v ] {
+                self.blocked[ v ] = false;
+                stack.append( &mut self.blocked_by[ v ] );
+            }
+        }
+    }
+}
+
+impl<I> Iterator for ElementaryCycles<I>
+where
+    I: Clone
+{
+    type Item = Vec<I>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        loop {
+            if self.path.is_empty() && !self.advance() {
+                return None;
+            }
+
+            while let Some( &( v, position, found ) ) = self.path.last() {
+                if let Some( &w ) = self.component[ v ].get( position ) {
+                    let length = self.path.len();
+                    let top = self.path.len() - 1;
+                    self.path[ top ].1 += 1;
+                    if w == self.start {
+                        self.path[ top ].2 = true;
+                        return Some( self.path.iter().map( |&( u, _, _ )| self.ids[ u ].clone() ).collect() );
+                    } else if !self.blocked[ w ] {
+                        if self.max_length.is_none_or( |max_length| length < max_length ) {
+                            self.blocked[ w ] = true;
+                            self.path.push( ( w, 0, false ) );
+                        } else {
+                            // A truncated branch must not block anything, treat it like a found cycle.
+                            self.path[ top ].2 = true;
+                        }
+                    }
+                } else {
+                    self.path.pop();
+                    if found {
+                        self.unblock( v );
+                    } else {
+                        for &w in self.component[ v ].iter() {
+                            if !self.blocked_by[ w ].contains( &v ) {
+                                self.blocked_by[ w ].push( v );
+                            }
+                        }
+                    }
+                    if let Some( parent ) = self.path.last_mut() {
+                        parent.2 |= found;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    pub fn has_cycle( &self ) -> bool {
+        self.toposort().is_err()
+    }
+
+    pub fn find_cycle( &self ) -> Option<Vec<I>> {
+        self.toposort().err().map( |Cycle( cycle )| cycle )
+    }
+
+    pub fn elementary_cycles( &self, max_length: Option<usize> ) -> ElementaryCycles<I> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        ElementaryCycles::new( indexed.ids, adjacency, max_length )
+    }
+
+    pub fn girth( &self ) -> Distance<usize> {
+        self.girth_by( |_| 1usize )
+    }
+
+    // Length of the shortest directed cycle, self loops included. Infinite for acyclic graphs. Weights
+    // must be non-negative, cycles through edges with negative weights are not counted.
+    pub fn girth_by<W, F>( &self, weight: F ) -> Distance<W>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let mut girth = None;
+        for id in self.data().keys() {
+            let paths = self.data().dijkstra( id.clone(), |_, _, edge| Some( weight( edge ) ), |_| false ).0;
+            for ( last, distance ) in paths.distances().iter() {
+                if let Some( closing ) = self.data().get_edge( last.clone(), id.clone() ).map( &weight ).filter( |closing| *closing >= W::zero() ) {
+                    let length = distance.clone() + closing;
+                    if girth.as_ref().is_none_or( |girth| length < *girth ) {
+                        girth = Some( length );
+                    }
+                }
+            }
+        }
+        Distance::from( girth )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{ GraphTraits, metrics::Distance },
+        directed_graph::DiGraph
+    };
+
+    fn complete( n: usize ) -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 0..n {
+            graph.add_node( id, () ).unwrap();
+        }
+        for id1 in 0..n {
+            for id2 in 0..n {
+                if id1 != id2 {
+                    graph.add_edge( id1, id2, () ).unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, () ).unwrap();
+        graph.add_edge( 2, 3, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+        assert!( !graph.has_cycle() );
+        assert!( graph.find_cycle().is_none() );
+
+        graph.add_edge( 4, 2, () ).unwrap();
+        assert!( graph.has_cycle() );
+        let mut cycle = graph.find_cycle().unwrap();
+        cycle.sort();
+        assert_eq!( cycle, vec![ 2, 3, 4 ] );
+    }
+
+    #[test]
+    fn test_elementary_cycles() {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, () ).unwrap();
+        graph.add_edge( 2, 1, () ).unwrap();
+        graph.add_edge( 2, 3, () ).unwrap();
+        graph.add_edge( 3, 1, () ).unwrap();
+        graph.add_edge( 3, 3, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+
+        let mut cycles: Vec<Vec<usize>> = graph.elementary_cycles( None ).collect();
+        cycles.sort();
+        assert_eq!( cycles, vec![ vec![ 1, 2 ], vec![ 1, 2, 3 ], vec![ 3 ] ] );
+    }
+
+    #[test]
+    fn test_elementary_cycles_complete() {
+        // Sum over k of C(n, k) * (k - 1)! for n = 5.
+        assert_eq!( complete( 5 ).elementary_cycles( None ).count(), 84 );
+        // Only the 2-cycles and 3-cycles: C(5, 2) + 2 * C(5, 3).
+        assert_eq!( complete( 5 ).elementary_cycles( Some( 3 ) ).count(), 30 );
+        assert_eq!( complete( 5 ).elementary_cycles( Some( 3 ) ).filter( |cycle| cycle.len() > 3 ).count(), 0 );
+    }
+
+    #[test]
+    fn test_elementary_cycles_lazy() {
+        let mut cycles = complete( 12 ).elementary_cycles( None );
+        assert_eq!( cycles.by_ref().take( 10 ).count(), 10 );
+        assert!( cycles.next().is_some() );
+    }
+
+    #[test]
+    fn test_girth() {
+        let mut graph = DiGraph::<usize, (), u32>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 1 ).unwrap();
+        graph.add_edge( 2, 3, 1 ).unwrap();
+        graph.add_edge( 1, 3, 1 ).unwrap();
+        assert_eq!( graph.girth(), Distance::Infinite );
+
+        graph.add_edge( 3, 1, 10 ).unwrap();
+        graph.add_edge( 3, 4, 1 ).unwrap();
+        graph.add_edge( 4, 2, 1 ).unwrap();
+        assert_eq!( graph.girth(), Distance::Finite( 2 ) );
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 3 ) );
+
+        graph.add_edge( 4, 4, 5 ).unwrap();
+        assert_eq!( graph.girth(), Distance::Finite( 1 ) );
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 3 ) );
+    }
+
+    #[test]
+    fn test_girth_negative_weights() {
+        let mut graph = DiGraph::<usize, (), i32>::new();
+        for id in 1..=3 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, -4 ).unwrap();
+        graph.add_edge( 2, 1, 1 ).unwrap();
+        graph.add_edge( 3, 3, -1 ).unwrap();
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Infinite );
+        graph.add_edge( 2, 3, 1 ).unwrap();
+        graph.add_edge( 3, 2, 2 ).unwrap();
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 3 ) );
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::{ BTreeMap, BTreeSet };
+
+use crate::{
+    graph::{
+        Error,
+        GraphAccess,
+        GraphTraits,
+        indexed::Indexed
+    },
+    directed_graph::DiGraph
+};
+
+// Immediate dominators and dominance frontiers of every node reachable from the root.
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct Dominators<I> {
+    root: I,
+    idom: BTreeMap<I, I>,
+    frontiers: BTreeMap<I, BTreeSet<I>>
+}
+
+impl<I> Dominators<I>
+where
+    I: Clone + Ord
+{
+    pub fn root( &self ) -> &I {
+        &self.root
+    }
+
+    // None for the root and for nodes the root cannot reach.
+    pub fn immediate_dominator( &self, node: &I ) -> Option<&I> {
+        self.idom.get( node )
+    }
+
+    pub fn is_reachable( &self, node: &I ) -> bool {
+        self.frontiers.contains_key( node )
+    }
+
+    // The node itself followed by its dominators up to the root.
+    pub fn dominators( &self, node: &I ) -> Option<Vec<I>> {
+        if !self.is_reachable( node ) {
+            return None;
+        }
+        let mut chain = vec![ node.clone() ];
+        while let Some( parent ) = self.idom.get( chain.last()? ) {
+            chain.push( parent.clone() );
+        }
+        Some( chain )
+    }
+
+    // Every node dominates itself.
+    pub fn dominates( &self, a: &I, b: &I ) -> bool {
+        self.dominators( b ).is_some_and( |chain| chain.contains( a ) )
+    }
+
+    pub fn strictly_dominates( &self, a: &I, b: &I ) -> bool {
+        a != b && self.dominates( a, b )
+    }
+
+    pub fn frontier( &self, node: &I ) -> Option<&BTreeSet<I>> {
+        self.frontiers.get( node )
+    }
+
+    pub fn frontiers( &self ) -> &BTreeMap<I, BTreeSet<I>> {
+        &self.frontiers
+    }
+
+    // Edges run from every immediate dominator to the nodes it dominates immediately.
+    pub fn tree( &self ) -> DiGraph<I, (), ()> {
+        let mut tree = DiGraph::new();
+        for node in self.frontiers.keys() {
+            let _ = tree.add_node( node.clone(), () );
+        }
+        for ( node, parent ) in self.idom.iter() {
+            let _ = tree.add_edge( parent.clone(), node.clone(), () );
+        }
+        tree
+    }
+}
+
+// Cooper, Harvey and Kennedy's iterative algorithm over a reverse postorder from `root`. Returns the
+// immediate dominator of every node, `usize::MAX` where unreachable, and the dominance frontiers.
+fn dominators( adjacency: &[Vec<usize>], root: usize ) -> ( Vec<usize>, Vec<BTreeSet<usize>> ) {
+    let n = adjacency.len();
+    let mut postorder = Vec::with_capacity( n );
+    let mut visited = vec![ false; n ];
+    let mut stack = vec![ ( root, 0 ) ];
+    visited[ root ] = true;
+    while let Some( frame ) = stack.last_mut() {
+        let v = frame.0;
+        if let Some( &w ) = adjacency[ v ].get( frame.1 ) {
+            frame.1 += 1;
+            if !visited[ w ] {
+                visited[ w ] = true;
+                stack.push( ( w, 0 ) );
+            }
+        } else {
+            postorder.push( v );
+            stack.pop();
+        }
+    }
+    let mut number = vec![ usize::MAX; n ];
+    postorder.iter().enumerate().for_each( |( i, &v )| number[ v ] = i );
+
+    let mut predecessors = vec![ Vec::new(); n ];
+    for &v in postorder.iter() {
+        adjacency[ v ].iter().for_each( |&w| predecessors[ w ].push( v ) );
+    }
+
+    let mut idom = vec![ usize::MAX; n ];
+    idom[ root ] = root;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &v in postorder.iter().rev().filter( |&&v| v != root ) {
+            let mut processed = predecessors[ v ].iter().copied().filter( |&p| idom[ p ] != usize::MAX );
+            let Some( first ) = processed.next() else {
+                continue;
+            };
+            let next = processed.fold( first, |mut a, mut b| {
+                while a != b {
+                    while number[ a ] < number[ b ] {
+                        a = idom[ a ];
+                    }
+                    while number[ b ] < number[ a ] {
+                        b = idom[ b ];
+                    }
+                }
+                a
+            } );
+            if idom[ v ] != next {
+                idom[ v ] = next;
+                changed = true;
+            }
+        }
+    }
+
+    // The root has an implicit predecessor outside the graph, so any edge back into it makes it a join
+    // point and the walk up from there has to include the root itself.
+    let mut frontiers = vec![ BTreeSet::new(); n ];
+    for &v in postorder.iter() {
+        if predecessors[ v ].len() + usize::from( v == root ) < 2 {
+            continue;
+        }
+        let stop = ( v != root ).then_some( idom[ v ] );
+        for &p in predecessors[ v ].iter() {
+            let mut runner = p;
+            while Some( runner ) != stop {
+                frontiers[ runner ].insert( v );
+                if runner == root {
+                    break;
+                }
+                runner = idom[ runner ];
+            }
+        }
+    }
+    ( idom, frontiers )
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn dominator_tree( indexed: &Indexed<'_, I, E>, adjacency: &[Vec<usize>], root: I ) -> Result<Dominators<I>, Error> {
+        let start = indexed.position( &root ).ok_or( Error::NodeNotFound )?;
+        let ( idom, frontiers ) = dominators( adjacency, start );
+        let reachable = |v: &usize| idom[ *v ] != usize::MAX;
+        Ok( Dominators {
+            root,
+            idom: ( 0..idom.len() ).filter( |v| reachable( v ) && *v != start )
+                .map( |v| ( indexed.ids[ v ].clone(), indexed.ids[ idom[ v ] ].clone() ) )
+                .collect(),
+            frontiers: frontiers.into_iter().enumerate()
+                .filter( |( v, _ )| reachable( v ) )
+                .map( |( v, frontier )| ( indexed.ids[ v ].clone(), frontier.into_iter().map( |w| indexed.ids[ w ].clone() ).collect() ) )
+                .collect()
+        } )
+    }
+
+    // Dominators of every node reachable from `entry`.
+    pub fn dominators( &self, entry: I ) -> Result<Dominators<I>, Error> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        Self::dominator_tree( &indexed, &adjacency, entry )
+    }
+
+    // Dominators of the reverse graph, covering every node that can reach `exit`. The frontiers are
+    // the post-dominance frontiers.
+    pub fn post_dominators( &self, exit: I ) -> Result<Dominators<I>, Error> {
+        let indexed = Indexed::new( self.data() );
+        let mut reversed = vec![ Vec::new(); indexed.ids.len() ];
+        for ( v, edges ) in indexed.neighbors().into_iter().enumerate() {
+            edges.into_iter().for_each( |w| reversed[ w ].push( v ) );
+        }
+        Self::dominator_tree( &indexed, &reversed, exit )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        graph::{ Error, GraphTraits },
+        directed_graph::DiGraph
+    };
+
+    // 1 -> 2, a loop 2 -> { 3, 4 } -> 5 -> 2 and the exit 2 -> 6. Node 7 is unreachable.
+    fn graph() -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=7 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 2, 3 ), ( 2, 4 ), ( 2, 6 ), ( 3, 5 ), ( 4, 5 ), ( 5, 2 ), ( 7, 5 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_dominators() {
+        let dominators = graph().dominators( 1 ).unwrap();
+        assert_eq!( dominators.root(), &1 );
+        assert_eq!( dominators.immediate_dominator( &1 ), None );
+        for id in 3..=6 {
+            assert_eq!( dominators.immediate_dominator( &id ), Some( &2 ) );
+        }
+        assert_eq!( dominators.immediate_dominator( &2 ), Some( &1 ) );
+        assert_eq!( dominators.dominators( &5 ), Some( vec![ 5, 2, 1 ] ) );
+        assert_eq!( dominators.dominators( &7 ), None );
+        assert!( dominators.dominates( &2, &5 ) );
+        assert!( dominators.dominates( &5, &5 ) );
+        assert!( !dominators.strictly_dominates( &5, &5 ) );
+        assert!( !dominators.dominates( &3, &5 ) );
+
+        let tree = dominators.tree();
+        assert_eq!( tree.order(), 6 );
+        assert!( tree.contains_edge( 2, 5 ) );
+
+        assert!( matches!( graph().dominators( 9 ), Err( Error::NodeNotFound ) ) );
+    }
+
+    #[test]
+    fn test_dominance_frontiers() {
+        let dominators = graph().dominators( 1 ).unwrap();
+        assert_eq!( dominators.frontier( &1 ), Some( &BTreeSet::new() ) );
+        assert_eq!( dominators.frontier( &2 ), Some( &BTreeSet::from( [ 2 ] ) ) );
+        assert_eq!( dominators.frontier( &3 ), Some( &BTreeSet::from( [ 5 ] ) ) );
+        assert_eq!( dominators.frontier( &4 ), Some( &BTreeSet::from( [ 5 ] ) ) );
+        assert_eq!( dominators.frontier( &5 ), Some( &BTreeSet::from( [ 2 ] ) ) );
+        assert_eq!( dominators.frontier( &6 ), Some( &BTreeSet::new() ) );
+        assert_eq!( dominators.frontier( &7 ), None );
+    }
+
+    #[test]
+    fn test_frontier_through_root() {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=3 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 2, 3 ), ( 3, 1 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        let dominators = graph.dominators( 1 ).unwrap();
+        for id in 1..=3 {
+            assert_eq!( dominators.frontier( &id ), Some( &BTreeSet::from( [ 1 ] ) ) );
+        }
+        assert_eq!( dominators.immediate_dominator( &3 ), Some( &2 ) );
+    }
+
+    #[test]
+    fn test_post_dominators() {
+        let post = graph().post_dominators( 6 ).unwrap();
+        assert_eq!( post.immediate_dominator( &2 ), Some( &6 ) );
+        assert_eq!( post.immediate_dominator( &1 ), Some( &2 ) );
+        assert_eq!( post.immediate_dominator( &5 ), Some( &2 ) );
+        assert_eq!( post.immediate_dominator( &3 ), Some( &5 ) );
+        assert_eq!( post.immediate_dominator( &7 ), Some( &5 ) );
+        assert!( post.dominates( &2, &3 ) );
+        assert_eq!( post.frontier( &3 ), Some( &BTreeSet::from( [ 2 ] ) ) );
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    directed_graph::DiGraph
+};
+
+// Hierholzer over adjacency lists, returns the node walk.
+fn hierholzer( adjacency: &[Vec<usize>], start: usize ) -> Vec<usize> {
+    let mut next = vec![ 0; adjacency.len() ];
+    let mut stack = vec![ start ];
+    let mut walk = Vec::new();
+    while let Some( &v ) = stack.last() {
+        if let Some( &w ) = adjacency[ v ].get( next[ v ] ) {
+            next[ v ] += 1;
+            stack.push( w );
+        } else {
+            walk.push( v );
+            stack.pop();
+        }
+    }
+    walk.reverse();
+    walk
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn euler( &self, closed: bool ) -> Option<Vec<( I, I )>> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        let size: usize = adjacency.iter().map( Vec::len ).sum();
+        if size == 0 {
+            return Some( Vec::new() );
+        }
+
+        let mut balance = vec![ 0isize; n ];
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            for &v in edges.iter() {
+                balance[ u ] += 1;
+                balance[ v ] -= 1;
+            }
+        }
+        let starts: Vec<usize> = ( 0..n ).filter( |&v| balance[ v ] == 1 ).collect();
+        let ends = ( 0..n ).filter( |&v| balance[ v ] == -1 ).count();
+        if balance.iter().any( |b| b.abs() > 1 ) {
+            return None;
+        }
+        let start = match ( starts.len(), ends ) {
+            ( 0, 0 ) => ( 0..n ).find( |&v| !adjacency[ v ].is_empty() )?,
+            ( 1, 1 ) if !closed => starts[ 0 ],
+            _ => return None
+        };
+
+        let walk = hierholzer( &adjacency, start );
+        ( walk.len() == size + 1 ).then( || walk.windows( 2 )
+            .map( |pair| ( indexed.ids[ pair[ 0 ] ].clone(), indexed.ids[ pair[ 1 ] ].clone() ) )
+            .collect()
+        )
+    }
+
+    pub fn eulerian_circuit( &self ) -> Option<Vec<( I, I )>> {
+        self.euler( true )
+    }
+
+    pub fn eulerian_path( &self ) -> Option<Vec<( I, I )>> {
+        self.euler( false )
+    }
+
+    pub fn is_eulerian( &self ) -> bool {
+        self.eulerian_circuit().is_some()
+    }
+
+    pub fn has_eulerian_path( &self ) -> bool {
+        self.eulerian_path().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::fixtures::digraph;
+
+    #[test]
+    fn test_eulerian_circuit() {
+        let graph = digraph( 6, &[ ( 1, 2 ), ( 2, 3 ), ( 3, 1 ), ( 3, 4 ), ( 4, 5 ), ( 5, 3 ) ] );
+        assert!( graph.is_eulerian() );
+        let circuit = graph.eulerian_circuit().unwrap();
+        assert_eq!( circuit.len(), 6 );
+        assert_eq!( circuit.first().unwrap().0, circuit.last().unwrap().1 );
+        for pair in circuit.windows( 2 ) {
+            assert_eq!( pair[ 0 ].1, pair[ 1 ].0 );
+        }
+    }
+
+    #[test]
+    fn test_eulerian_path() {
+        let graph = digraph( 5, &[ ( 1, 2 ), ( 2, 3 ), ( 3, 1 ), ( 1, 4 ) ] );
+        assert!( !graph.is_eulerian() );
+        assert!( graph.has_eulerian_path() );
+        let path = graph.eulerian_path().unwrap();
+        assert_eq!( path.first().unwrap().0, 1 );
+        assert_eq!( path.last().unwrap().1, 4 );
+        assert_eq!( path.len(), 4 );
+
+        assert!( !digraph( 4, &[ ( 1, 2 ), ( 1, 3 ) ] ).has_eulerian_path() );
+        assert!( !digraph( 5, &[ ( 1, 2 ), ( 2, 1 ), ( 3, 4 ), ( 4, 3 ) ] ).has_eulerian_path() );
+    }
+}
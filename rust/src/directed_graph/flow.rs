@@ -0,0 +1,371 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    collections::{ BTreeMap, BTreeSet, VecDeque },
+    ops::Sub
+};
+
+use thiserror::Error;
+
+use crate::{
+    graph::{
+        Error as GraphError,
+        GraphAccess,
+        indexed::Indexed,
+        weight::Weight
+    },
+    directed_graph::DiGraph
+};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Graph Error: {0}")]
+    GraphError( #[from] GraphError ),
+    #[error("Flow Error: Source And Sink Are The Same Node")]
+    SourceIsSink,
+    #[error("Flow Error: Supplies And Demands Do Not Balance")]
+    Unbalanced,
+    #[error("Flow Error: Demands Cannot Be Satisfied")]
+    Infeasible,
+    #[error("Flow Error: Negative Cost Cycle Detected")]
+    NegativeCostCycle
+}
+
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct MaxFlow<I, W> {
+    value: W,
+    flows: BTreeMap<( I, I ), W>,
+    source_side: BTreeSet<I>,
+    sink_side: BTreeSet<I>,
+    cut: Vec<( I, I )>
+}
+
+impl<I, W> MaxFlow<I, W>
+where
+    I: Clone + Ord
+{
+    pub fn value( &self ) -> &W {
+        &self.value
+    }
+
+    pub fn flows( &self ) -> &BTreeMap<( I, I ), W> {
+        &self.flows
+    }
+
+    pub fn flow( &self, from: &I, to: &I ) -> Option<&W> {
+        self.flows.get( &( from.clone(), to.clone() ) )
+    }
+
+    // Nodes still reachable from the source in the residual network, and all others.
+    pub fn min_cut( &self ) -> ( &BTreeSet<I>, &BTreeSet<I> ) {
+        ( &self.source_side, &self.sink_side )
+    }
+
+    pub fn cut_edges( &self ) -> &[( I, I )] {
+        &self.cut
+    }
+}
+
+// Residual network, edge `2k` is the k-th original edge and `2k + 1` its reverse.
+pub(crate) struct Residual<W> {
+    pub heads: Vec<usize>,
+    pub capacities: Vec<W>,
+    pub originals: Vec<W>,
+    pub adjacency: Vec<Vec<usize>>
+}
+
+impl<W> Residual<W>
+where
+    W: Weight + Sub<Output = W>
+{
+    pub fn new<I, E, F>( indexed: &Indexed<'_, I, E>, capacity: F ) -> Self
+    where
+        I: Clone + Ord,
+        F: Fn( &E ) -> W
+    {
+        let mut residual = Self {
+            heads: Vec::new(),
+            capacities: Vec::new(),
+            originals: Vec::new(),
+            adjacency: vec![ Vec::new(); indexed.ids.len() ]
+        };
+        for ( u, edges ) in indexed.adjacency.iter().enumerate() {
+            for &( v, edge ) in edges.iter() {
+                let capacity = capacity( edge ).max( W::zero() );
+                residual.add_edge( u, v, capacity );
+            }
+        }
+        residual
+    }
+
+    pub fn add_edge( &mut self, u: usize, v: usize, capacity: W ) {
+        self.adjacency[ u ].push( self.heads.len() );
+        self.heads.push( v );
+        self.capacities.push( capacity.clone() );
+        self.originals.push( capacity );
+        self.adjacency[ v ].push( self.heads.len() );
+        self.heads.push( u );
+        self.capacities.push( W::zero() );
+        self.originals.push( W::zero() );
+    }
+
+    pub fn tail( &self, edge: usize ) -> usize {
+        self.heads[ edge ^ 1 ]
+    }
+
+    pub fn push( &mut self, edge: usize, amount: W ) {
+        self.capacities[ edge ] = self.capacities[ edge ].clone() - amount.clone();
+        self.capacities[ edge ^ 1 ] = self.capacities[ edge ^ 1 ].clone() + amount;
+    }
+
+    pub fn flow( &self, edge: usize ) -> W {
+        let flow = self.originals[ edge ].clone() - self.capacities[ edge ].clone();
+        flow.max( W::zero() )
+    }
+
+    fn levels( &self, source: usize ) -> Vec<usize> {
+        let mut levels = vec![ usize::MAX; self.adjacency.len() ];
+        let mut queue = VecDeque::from( [ source ] );
+        levels[ source ] = 0;
+        while let Some( u ) = queue.pop_front() {
+            for &edge in self.adjacency[ u ].iter() {
+                let v = self.heads[ edge ];
+                if levels[ v ] == usize::MAX && self.capacities[ edge ] > W::zero() {
+                    levels[ v ] = levels[ u ] + 1;
+                    queue.push_back( v );
+                }
+            }
+        }
+        levels
+    }
+
+    fn edmonds_karp( &mut self, source: usize, sink: usize ) -> W {
+        let mut total = W::zero();
+        loop {
+            let mut via = vec![ usize::MAX; self.adjacency.len() ];
+            let mut queue = VecDeque::from( [ source ] );
+            while let Some( u ) = queue.pop_front() {
+                for &edge in self.adjacency[ u ].iter() {
+                    let v = self.heads[ edge ];
+                    if v != source && via[ v ] == usize::MAX && self.capacities[ edge ] > W::zero() {
+                        via[ v ] = edge;
+                        queue.push_back( v );
+                    }
+                }
+            }
+            if via[ sink ] == usize::MAX {
+                return total;
+            }
+
+            let mut path = Vec::new();
+            let mut current = sink;
+            while current != source {
+                path.push( via[ current ] );
+                current = self.tail( via[ current ] );
+            }
+            let Some( bottleneck ) = path.iter().map( |&edge| self.capacities[ edge ].clone() ).min() else {
+                return total;
+            };
+            path.into_iter().for_each( |edge| self.push( edge, bottleneck.clone() ) );
+            total = total + bottleneck;
+        }
+    }
+
+    // One augmenting path through the level graph, `next` keeps the first edge of every node that may
+    // still lead to the sink. Frames hold a node and the edge taken out of it, a dead end drops its frame
+    // and skips that edge in the node before.
+    fn augment( &mut self, source: usize, sink: usize, levels: &[usize], next: &mut [usize] ) -> W {
+        let mut frames: Vec<( usize, usize )> = Vec::new();
+        let mut u = source;
+        while u != sink {
+            let mut admissible = None;
+            while let Some( &edge ) = self.adjacency[ u ].get( next[ u ] ) {
+                if levels[ self.heads[ edge ] ] == levels[ u ] + 1 && self.capacities[ edge ] > W::zero() {
+                    admissible = Some( edge );
+                    break;
+                }
+                next[ u ] += 1;
+            }
+            if let Some( edge ) = admissible {
+                frames.push( ( u, edge ) );
+                u = self.heads[ edge ];
+            } else {
+                let Some( ( parent, _ ) ) = frames.pop() else {
+                    return W::zero();
+                };
+                next[ parent ] += 1;
+                u = parent;
+            }
+        }
+        let Some( bottleneck ) = frames.iter().map( |&( _, edge )| self.capacities[ edge ].clone() ).min() else {
+            return W::zero();
+        };
+        frames.into_iter().for_each( |( _, edge )| self.push( edge, bottleneck.clone() ) );
+        bottleneck
+    }
+
+    fn dinic( &mut self, source: usize, sink: usize ) -> W {
+        let mut total = W::zero();
+        loop {
+            let levels = self.levels( source );
+            if levels[ sink ] == usize::MAX {
+                return total;
+            }
+            let mut next = vec![ 0; self.adjacency.len() ];
+            loop {
+                let pushed = self.augment( source, sink, &levels, &mut next );
+                if pushed <= W::zero() {
+                    break;
+                }
+                total = total + pushed;
+            }
+        }
+    }
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn max_flow<W, F, A>( &self, source: I, sink: I, capacity: F, algorithm: A ) -> Result<MaxFlow<I, W>, Error>
+    where
+        W: Weight + Sub<Output = W>,
+        F: Fn( &E ) -> W,
+        A: FnOnce( &mut Residual<W>, usize, usize ) -> W
+    {
+        let indexed = Indexed::new( self.data() );
+        let s = indexed.position( &source ).ok_or( GraphError::NodeNotFound )?;
+        let t = indexed.position( &sink ).ok_or( GraphError::NodeNotFound )?;
+        if s == t {
+            return Err( Error::SourceIsSink );
+        }
+
+        let mut residual = Residual::new( &indexed, capacity );
+        let value = algorithm( &mut residual, s, t );
+
+        let reachable = residual.levels( s );
+        let source_side: BTreeSet<I> = ( 0..indexed.ids.len() )
+            .filter( |&i| reachable[ i ] != usize::MAX )
+            .map( |i| indexed.ids[ i ].clone() )
+            .collect();
+        let sink_side: BTreeSet<I> = ( 0..indexed.ids.len() )
+            .filter( |&i| reachable[ i ] == usize::MAX )
+            .map( |i| indexed.ids[ i ].clone() )
+            .collect();
+
+        let mut flows = BTreeMap::new();
+        let mut cut = Vec::new();
+        for edge in ( 0..residual.heads.len() ).step_by( 2 ) {
+            let ( u, v ) = ( residual.tail( edge ), residual.heads[ edge ] );
+            let key = ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() );
+            if reachable[ u ] != usize::MAX && reachable[ v ] == usize::MAX && residual.originals[ edge ] > W::zero() {
+                cut.push( key.clone() );
+            }
+            flows.insert( key, residual.flow( edge ) );
+        }
+
+        Ok( MaxFlow { value, flows, source_side, sink_side, cut } )
+    }
+
+    pub fn edmonds_karp<W, F>( &self, source: I, sink: I, capacity: F ) -> Result<MaxFlow<I, W>, Error>
+    where
+        W: Weight + Sub<Output = W>,
+        F: Fn( &E ) -> W
+    {
+        self.max_flow( source, sink, capacity, Residual::edmonds_karp )
+    }
+
+    pub fn dinic<W, F>( &self, source: I, sink: I, capacity: F ) -> Result<MaxFlow<I, W>, Error>
+    where
+        W: Weight + Sub<Output = W>,
+        F: Fn( &E ) -> W
+    {
+        self.max_flow( source, sink, capacity, Residual::dinic )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{
+            Error as GraphError,
+            GraphTraits
+        },
+        directed_graph::{
+            DiGraph,
+            flow::Error
+        }
+    };
+
+    fn graph() -> DiGraph<&'static str, (), u32> {
+        let mut graph = DiGraph::<&'static str, (), u32>::new();
+        for id in [ "s", "a", "b", "c", "d", "t" ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( "s", "a", 16 ).unwrap();
+        graph.add_edge( "s", "b", 13 ).unwrap();
+        graph.add_edge( "a", "b", 10 ).unwrap();
+        graph.add_edge( "b", "a", 4 ).unwrap();
+        graph.add_edge( "a", "c", 12 ).unwrap();
+        graph.add_edge( "c", "b", 9 ).unwrap();
+        graph.add_edge( "b", "d", 14 ).unwrap();
+        graph.add_edge( "d", "c", 7 ).unwrap();
+        graph.add_edge( "c", "t", 20 ).unwrap();
+        graph.add_edge( "d", "t", 4 ).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_max_flow() {
+        let graph = graph();
+        for flow in [ graph.edmonds_karp( "s", "t", |capacity| *capacity ), graph.dinic( "s", "t", |capacity| *capacity ) ] {
+            let flow = flow.unwrap();
+            assert_eq!( *flow.value(), 23 );
+            for ( ( u, v ), amount ) in flow.flows().iter() {
+                assert!( amount <= graph.get_edge( *u, *v ).unwrap() );
+            }
+            for id in [ "a", "b", "c", "d" ] {
+                let incoming: u32 = flow.flows().iter().filter( |( ( _, v ), _ )| *v == id ).map( |( _, amount )| amount ).sum();
+                let outgoing: u32 = flow.flows().iter().filter( |( ( u, _ ), _ )| *u == id ).map( |( _, amount )| amount ).sum();
+                assert_eq!( incoming, outgoing );
+            }
+            let cut: u32 = flow.cut_edges().iter().map( |( u, v )| graph.get_edge( *u, *v ).unwrap() ).sum();
+            assert_eq!( cut, 23 );
+            let ( source_side, sink_side ) = flow.min_cut();
+            assert!( source_side.contains( "s" ) && sink_side.contains( "t" ) );
+            assert_eq!( source_side.len() + sink_side.len(), 6 );
+        }
+    }
+
+    #[test]
+    fn test_max_flow_disconnected() {
+        let mut graph = graph();
+        graph.delete_edge( "c", "t" ).unwrap();
+        graph.delete_edge( "d", "t" ).unwrap();
+        let flow = graph.dinic( "s", "t", |capacity| *capacity ).unwrap();
+        assert_eq!( *flow.value(), 0 );
+        assert!( flow.cut_edges().is_empty() );
+        assert_eq!( flow.flow( &"s", &"a" ), Some( &0 ) );
+    }
+
+    #[test]
+    fn test_max_flow_errors() {
+        let graph = graph();
+        assert!( matches!( graph.edmonds_karp( "s", "s", |capacity| *capacity ), Err( Error::SourceIsSink ) ) );
+        assert!( matches!( graph.dinic( "s", "x", |capacity| *capacity ), Err( Error::GraphError( GraphError::NodeNotFound ) ) ) );
+    }
+
+    #[test]
+    fn test_max_flow_long_path() {
+        let mut graph = DiGraph::<usize, (), u32>::new();
+        for id in 0..100_000 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for id in 1..100_000 {
+            graph.add_edge( id - 1, id, 1 + ( id % 7 ) as u32 ).unwrap();
+        }
+        let flow = graph.dinic( 0, 99_999, |capacity| *capacity ).unwrap();
+        assert_eq!( *flow.value(), 1 );
+    }
+}
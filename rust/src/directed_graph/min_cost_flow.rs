@@ -0,0 +1,255 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Reverse,
+    collections::{ BTreeMap, BinaryHeap },
+    ops::{ Mul, Neg, Sub }
+};
+
+use crate::{
+    graph::{
+        Error as GraphError,
+        GraphAccess,
+        indexed::Indexed,
+        shortest_path::bellman_ford,
+        weight::Weight
+    },
+    directed_graph::{
+        DiGraph,
+        flow::{ Error, Residual }
+    }
+};
+
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct MinCostFlow<I, W> {
+    cost: W,
+    flows: BTreeMap<( I, I ), W>
+}
+
+impl<I, W> MinCostFlow<I, W>
+where
+    I: Clone + Ord
+{
+    pub fn cost( &self ) -> &W {
+        &self.cost
+    }
+
+    pub fn flows( &self ) -> &BTreeMap<( I, I ), W> {
+        &self.flows
+    }
+
+    pub fn flow( &self, from: &I, to: &I ) -> Option<&W> {
+        self.flows.get( &( from.clone(), to.clone() ) )
+    }
+}
+
+// Dijkstra over reduced costs `cost + potential( u ) - potential( v )`, which stay non-negative
+// between augmentations. Returns the edge used to reach every node.
+fn reduced_dijkstra<W>( residual: &Residual<W>, costs: &[W], potentials: &[W], source: usize ) -> ( Vec<Option<W>>, Vec<usize> )
+where
+    W: Weight + Sub<Output = W>
+{
+    let n = residual.adjacency.len();
+    let mut distances: Vec<Option<W>> = ( 0..n ).map( |_| None ).collect();
+    let mut via = vec![ usize::MAX; n ];
+    let mut heap = BinaryHeap::new();
+    distances[ source ] = Some( W::zero() );
+    heap.push( Reverse( ( W::zero(), source ) ) );
+
+    while let Some( Reverse( ( distance, u ) ) ) = heap.pop() {
+        if distances[ u ].as_ref().is_some_and( |best| distance > *best ) {
+            continue;
+        }
+        for &edge in residual.adjacency[ u ].iter() {
+            if residual.capacities[ edge ] <= W::zero() {
+                continue;
+            }
+            let v = residual.heads[ edge ];
+            let next = distance.clone() + costs[ edge ].clone() + potentials[ u ].clone() - potentials[ v ].clone();
+            if distances[ v ].as_ref().is_none_or( |best| next < *best ) {
+                distances[ v ] = Some( next.clone() );
+                via[ v ] = edge;
+                heap.push( Reverse( ( next, v ) ) );
+            }
+        }
+    }
+    ( distances, via )
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Successive shortest paths. Positive supplies are sources, negative supplies are demands,
+    // and every unit of supply has to reach a demand. Any negative cost cycle with spare capacity
+    // fails with `NegativeCostCycle`, even one that no supply can reach. `W` has to be signed, the
+    // residual graph carries negated costs.
+    pub fn min_cost_flow<W, F, C>( &self, supplies: &BTreeMap<I, W>, capacity: F, cost: C ) -> Result<MinCostFlow<I, W>, Error>
+    where
+        W: Weight + Sub<Output = W> + Mul<Output = W> + Neg<Output = W>,
+        F: Fn( &E ) -> W,
+        C: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self.data() );
+        let n = indexed.ids.len();
+        let mut residual = Residual::new( &indexed, capacity );
+        let mut costs = Vec::with_capacity( residual.heads.len() );
+        for &( _, edge ) in indexed.adjacency.iter().flatten() {
+            let cost = cost( edge );
+            costs.push( cost.clone() );
+            costs.push( -cost );
+        }
+        let originals = residual.heads.len();
+
+        let ( source, sink ) = ( n, n + 1 );
+        residual.adjacency.push( Vec::new() );
+        residual.adjacency.push( Vec::new() );
+        let mut supplied = W::zero();
+        let mut demanded = W::zero();
+        for ( id, supply ) in supplies.iter() {
+            let node = indexed.position( id ).ok_or( GraphError::NodeNotFound )?;
+            if *supply > W::zero() {
+                residual.add_edge( source, node, supply.clone() );
+                supplied = supplied + supply.clone();
+            } else if *supply < W::zero() {
+                residual.add_edge( node, sink, -supply.clone() );
+                demanded = demanded + -supply.clone();
+            } else {
+                continue;
+            }
+            costs.push( W::zero() );
+            costs.push( W::zero() );
+        }
+        if supplied != demanded {
+            return Err( Error::Unbalanced );
+        }
+
+        let open: Vec<Vec<( usize, W )>> = residual.adjacency.iter()
+            .map( |edges| edges.iter()
+                .filter( |&&edge| residual.capacities[ edge ] > W::zero() )
+                .map( |&edge| ( residual.heads[ edge ], costs[ edge ].clone() ) )
+                .collect()
+            )
+            .collect();
+        // Starting from every node at once, as from a virtual source with free edges to all of them.
+        let everywhere: Vec<usize> = ( 0..open.len() ).collect();
+        let mut potentials: Vec<W> = bellman_ford( &open, &everywhere )
+            .map_err( |_| Error::NegativeCostCycle )?
+            .distances.into_iter()
+            .map( |distance| distance.unwrap_or( W::zero() ) )
+            .collect();
+
+        let mut flowed = W::zero();
+        while flowed < supplied {
+            let ( distances, via ) = reduced_dijkstra( &residual, &costs, &potentials, source );
+            if distances[ sink ].is_none() {
+                return Err( Error::Infeasible );
+            }
+            for ( potential, distance ) in potentials.iter_mut().zip( distances ) {
+                if let Some( distance ) = distance {
+                    *potential = potential.clone() + distance;
+                }
+            }
+
+            let mut path = Vec::new();
+            let mut current = sink;
+            while current != source {
+                path.push( via[ current ] );
+                current = residual.tail( via[ current ] );
+            }
+            let Some( bottleneck ) = path.iter().map( |&edge| residual.capacities[ edge ].clone() ).min() else {
+                return Err( Error::Infeasible );
+            };
+            path.into_iter().for_each( |edge| residual.push( edge, bottleneck.clone() ) );
+            flowed = flowed + bottleneck;
+        }
+
+        let mut total = W::zero();
+        let mut flows = BTreeMap::new();
+        for edge in ( 0..originals ).step_by( 2 ) {
+            let flow = residual.flow( edge );
+            total = total + flow.clone() * costs[ edge ].clone();
+            flows.insert( ( indexed.ids[ residual.tail( edge ) ].clone(), indexed.ids[ residual.heads[ edge ] ].clone() ), flow );
+        }
+        Ok( MinCostFlow { cost: total, flows } )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        graph::GraphTraits,
+        directed_graph::{
+            DiGraph,
+            flow::Error
+        }
+    };
+
+    // ( capacity, cost )
+    fn graph() -> DiGraph<char, (), ( i64, i64 )> {
+        let mut graph = DiGraph::<char, (), ( i64, i64 )>::new();
+        for id in [ 'a', 'b', 'c', 'd' ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 'a', 'b', ( 4, 2 ) ).unwrap();
+        graph.add_edge( 'a', 'c', ( 2, 2 ) ).unwrap();
+        graph.add_edge( 'b', 'c', ( 2, 1 ) ).unwrap();
+        graph.add_edge( 'b', 'd', ( 3, 3 ) ).unwrap();
+        graph.add_edge( 'c', 'd', ( 5, 1 ) ).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_min_cost_flow() {
+        let graph = graph();
+        let supplies = BTreeMap::from( [ ( 'a', 4 ), ( 'd', -4 ) ] );
+        let flow = graph.min_cost_flow( &supplies, |edge| edge.0, |edge| edge.1 ).unwrap();
+        assert_eq!( *flow.cost(), 14 );
+        assert_eq!( flow.flow( &'a', &'c' ), Some( &2 ) );
+        assert_eq!( flow.flow( &'b', &'c' ), Some( &2 ) );
+        assert_eq!( flow.flow( &'c', &'d' ), Some( &4 ) );
+        assert_eq!( flow.flow( &'b', &'d' ), Some( &0 ) );
+    }
+
+    #[test]
+    fn test_min_cost_flow_negative_costs() {
+        let mut graph = graph();
+        *graph.get_edge_mut( 'b', 'd' ).unwrap() = ( 3, -3 );
+        let supplies = BTreeMap::from( [ ( 'a', 3 ), ( 'c', 1 ), ( 'd', -4 ) ] );
+        let flow = graph.min_cost_flow( &supplies, |edge| edge.0, |edge| edge.1 ).unwrap();
+        assert_eq!( *flow.cost(), -2 );
+        assert_eq!( flow.flow( &'b', &'d' ), Some( &3 ) );
+        assert_eq!( flow.flow( &'c', &'d' ), Some( &1 ) );
+    }
+
+    #[test]
+    fn test_min_cost_flow_errors() {
+        let graph = graph();
+        let unbalanced = BTreeMap::from( [ ( 'a', 4 ), ( 'd', -3 ) ] );
+        assert!( matches!( graph.min_cost_flow( &unbalanced, |edge| edge.0, |edge| edge.1 ), Err( Error::Unbalanced ) ) );
+        let infeasible = BTreeMap::from( [ ( 'a', 7 ), ( 'd', -7 ) ] );
+        assert!( matches!( graph.min_cost_flow( &infeasible, |edge| edge.0, |edge| edge.1 ), Err( Error::Infeasible ) ) );
+        let backwards = BTreeMap::from( [ ( 'd', 1 ), ( 'a', -1 ) ] );
+        assert!( matches!( graph.min_cost_flow( &backwards, |edge| edge.0, |edge| edge.1 ), Err( Error::Infeasible ) ) );
+        let missing = BTreeMap::from( [ ( 'z', 1 ), ( 'a', -1 ) ] );
+        assert!( matches!( graph.min_cost_flow( &missing, |edge| edge.0, |edge| edge.1 ), Err( Error::GraphError( _ ) ) ) );
+    }
+
+    #[test]
+    fn test_min_cost_flow_detached_negative_cycle() {
+        let mut graph = graph();
+        graph.add_node( 'x', () ).unwrap();
+        graph.add_node( 'y', () ).unwrap();
+        graph.add_edge( 'x', 'y', ( 1, -2 ) ).unwrap();
+        graph.add_edge( 'y', 'x', ( 1, 1 ) ).unwrap();
+        let supplies = BTreeMap::from( [ ( 'a', 4 ), ( 'd', -4 ) ] );
+        assert!( matches!( graph.min_cost_flow( &supplies, |edge| edge.0, |edge| edge.1 ), Err( Error::NegativeCostCycle ) ) );
+
+        // Without spare capacity the cycle cannot carry flow and does not count.
+        *graph.get_edge_mut( 'y', 'x' ).unwrap() = ( 0, 1 );
+        assert_eq!( *graph.min_cost_flow( &supplies, |edge| edge.0, |edge| edge.1 ).unwrap().cost(), 14 );
+    }
+}
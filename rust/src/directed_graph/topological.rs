@@ -0,0 +1,208 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Reverse,
+    collections::{ BinaryHeap, VecDeque }
+};
+
+use crate::{
+    graph::{
+        Cycle,
+        GraphAccess,
+        indexed::Indexed
+    },
+    directed_graph::DiGraph
+};
+
+fn in_degrees( adjacency: &[Vec<usize>] ) -> Vec<usize> {
+    let mut degrees = vec![ 0; adjacency.len() ];
+    adjacency.iter().flatten().for_each( |&j| degrees[ j ] += 1 );
+    degrees
+}
+
+// Every node left over by Kahn's algorithm has a left over predecessor, so walking
+// predecessors must eventually revisit a node and close a cycle.
+fn leftover_cycle( adjacency: &[Vec<usize>], degrees: &[usize] ) -> Vec<usize> {
+    let n = adjacency.len();
+    let mut predecessor = vec![ usize::MAX; n ];
+    for ( i, edges ) in adjacency.iter().enumerate() {
+        if degrees[ i ] > 0 {
+            edges.iter().filter( |&&j| degrees[ j ] > 0 ).for_each( |&j| predecessor[ j ] = i );
+        }
+    }
+
+    let mut seen = vec![ false; n ];
+    let mut current = ( 0..n ).find( |&i| degrees[ i ] > 0 ).unwrap_or( 0 );
+    while !seen[ current ] {
+        seen[ current ] = true;
+        current = predecessor[ current ];
+    }
+
+    let start = current;
+    let mut cycle = vec![ start ];
+    current = predecessor[ start ];
+    while current != start {
+        cycle.push( current );
+        current = predecessor[ current ];
+    }
+    cycle.reverse();
+    cycle.rotate_right( 1 );
+    cycle
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn kahn<F>( &self, mut next: F ) -> Result<Vec<Vec<I>>, Cycle<I>>
+    where
+        F: FnMut( &[Vec<usize>], &mut Vec<usize> ) -> Vec<Vec<usize>>
+    {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let mut degrees = in_degrees( &adjacency );
+        let groups = next( &adjacency, &mut degrees );
+        if degrees.iter().any( |&degree| degree > 0 ) {
+            let cycle = leftover_cycle( &adjacency, &degrees );
+            return Err( Cycle( cycle.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() ) );
+        }
+        Ok( groups.into_iter()
+            .map( |group| group.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() )
+            .collect()
+        )
+    }
+
+    pub fn toposort( &self ) -> Result<Vec<I>, Cycle<I>> {
+        self.kahn( |adjacency, degrees| {
+            let mut order = Vec::new();
+            let mut queue: VecDeque<usize> = ( 0..adjacency.len() ).filter( |&i| degrees[ i ] == 0 ).collect();
+            while let Some( i ) = queue.pop_front() {
+                order.push( i );
+                for &j in adjacency[ i ].iter() {
+                    degrees[ j ] -= 1;
+                    if degrees[ j ] == 0 {
+                        queue.push_back( j );
+                    }
+                }
+            }
+            vec![ order ]
+        }).map( |mut groups| groups.pop().unwrap_or_default() )
+    }
+
+    // Indices follow the ordering of the ids, so the smallest index is the smallest id.
+    pub fn toposort_lexicographic( &self ) -> Result<Vec<I>, Cycle<I>> {
+        self.kahn( |adjacency, degrees| {
+            let mut order = Vec::new();
+            let mut heap: BinaryHeap<Reverse<usize>> = ( 0..adjacency.len() )
+                .filter( |&i| degrees[ i ] == 0 )
+                .map( Reverse )
+                .collect();
+            while let Some( Reverse( i ) ) = heap.pop() {
+                order.push( i );
+                for &j in adjacency[ i ].iter() {
+                    degrees[ j ] -= 1;
+                    if degrees[ j ] == 0 {
+                        heap.push( Reverse( j ) );
+                    }
+                }
+            }
+            vec![ order ]
+        }).map( |mut groups| groups.pop().unwrap_or_default() )
+    }
+
+    pub fn toposort_layers( &self ) -> Result<Vec<Vec<I>>, Cycle<I>> {
+        self.kahn( |adjacency, degrees| {
+            let mut layers = Vec::new();
+            let mut layer: Vec<usize> = ( 0..adjacency.len() ).filter( |&i| degrees[ i ] == 0 ).collect();
+            while !layer.is_empty() {
+                let mut next = Vec::new();
+                for &i in layer.iter() {
+                    for &j in adjacency[ i ].iter() {
+                        degrees[ j ] -= 1;
+                        if degrees[ j ] == 0 {
+                            next.push( j );
+                        }
+                    }
+                }
+                next.sort_unstable();
+                layers.push( std::mem::replace( &mut layer, next ) );
+            }
+            layers
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{
+            Cycle,
+            GraphTraits
+        },
+        directed_graph::DiGraph
+    };
+
+    fn graph() -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=6 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 5, 1, () ).unwrap();
+        graph.add_edge( 5, 2, () ).unwrap();
+        graph.add_edge( 6, 2, () ).unwrap();
+        graph.add_edge( 2, 3, () ).unwrap();
+        graph.add_edge( 1, 4, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+        graph
+    }
+
+    fn assert_valid_order( graph: &DiGraph<usize, (), ()>, order: &[usize] ) {
+        assert_eq!( order.len(), graph.order() );
+        let position = |id: &usize| order.iter().position( |other| other == id ).unwrap();
+        for ( id, node ) in graph.nodes().iter() {
+            for next_id in node.adjacencies().keys() {
+                assert!( position( id ) < position( next_id ) );
+            }
+        }
+    }
+
+    #[test]
+    fn test_toposort() {
+        let graph = graph();
+        assert_valid_order( &graph, &graph.toposort().unwrap() );
+    }
+
+    #[test]
+    fn test_toposort_lexicographic() {
+        let graph = graph();
+        assert_eq!( graph.toposort_lexicographic().unwrap(), vec![ 5, 1, 6, 2, 3, 4 ] );
+    }
+
+    #[test]
+    fn test_toposort_layers() {
+        let graph = graph();
+        assert_eq!( graph.toposort_layers().unwrap(), vec![ vec![ 5, 6 ], vec![ 1, 2 ], vec![ 3 ], vec![ 4 ] ] );
+    }
+
+    #[test]
+    fn test_toposort_cycle() {
+        let mut graph = graph();
+        graph.add_edge( 4, 6, () ).unwrap();
+        let Cycle( cycle ) = graph.toposort().unwrap_err();
+        assert!( !cycle.is_empty() );
+        for ( i, id ) in cycle.iter().enumerate() {
+            assert!( graph.contains_edge( *id, cycle[ ( i + 1 ) % cycle.len() ] ) );
+        }
+        assert!( graph.toposort_lexicographic().is_err() );
+        assert!( graph.toposort_layers().is_err() );
+    }
+
+    #[test]
+    fn test_toposort_self_loop() {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        graph.add_node( 1, () ).unwrap();
+        graph.add_edge( 1, 1, () ).unwrap();
+        assert_eq!( graph.toposort(), Err( Cycle( vec![ 1 ] ) ) );
+    }
+}
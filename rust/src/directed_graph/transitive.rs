@@ -0,0 +1,197 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        Cycle,
+        GraphAccess,
+        GraphTraits,
+        indexed::Indexed
+    },
+    directed_graph::{
+        DiGraph,
+        components::tarjan
+    }
+};
+
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct Reachability<I> {
+    ids: Vec<I>,
+    reachable: Vec<Vec<bool>>
+}
+
+impl<I> Reachability<I>
+where
+    I: Clone + Ord
+{
+    pub fn ids( &self ) -> &[I] {
+        &self.ids
+    }
+
+    // Rows and columns follow `ids`.
+    pub fn matrix( &self ) -> &[Vec<bool>] {
+        &self.reachable
+    }
+
+    // A node only reaches itself through a cycle.
+    pub fn reaches( &self, from: &I, to: &I ) -> bool {
+        match ( self.ids.binary_search( from ), self.ids.binary_search( to ) ) {
+            ( Ok( from ), Ok( to ) ) => self.reachable[ from ][ to ],
+            _ => false
+        }
+    }
+}
+
+// Reachability over paths of at least one edge. Components come sinks first, so every component
+// can take the union of the rows of the components it points at.
+fn reachable( adjacency: &[Vec<usize>] ) -> Vec<Vec<bool>> {
+    let n = adjacency.len();
+    let mut rows = vec![ vec![ false; n ]; n ];
+    for component in tarjan( adjacency ) {
+        let mut row = vec![ false; n ];
+        for &v in component.iter() {
+            for &w in adjacency[ v ].iter() {
+                row[ w ] = true;
+                if component.binary_search( &w ).is_err() {
+                    row.iter_mut().zip( rows[ w ].iter() ).for_each( |( a, &b )| *a |= b );
+                }
+            }
+        }
+        if component.len() > 1 {
+            component.iter().for_each( |&v| row[ v ] = true );
+        }
+        component.iter().for_each( |&v| rows[ v ] = row.clone() );
+    }
+    rows
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    pub fn reachability_matrix( &self ) -> Reachability<I> {
+        let indexed = Indexed::new( self.data() );
+        let reachable = reachable( &indexed.neighbors() );
+        Reachability { ids: indexed.ids, reachable }
+    }
+
+    // An edge for every pair joined by a path, self loops only on nodes that lie on a cycle.
+    pub fn transitive_closure( &self ) -> DiGraph<I, N, ()>
+    where
+        N: Clone + PartialEq
+    {
+        let Reachability { ids, reachable } = self.reachability_matrix();
+        let mut closure = DiGraph::new();
+        for ( id, node ) in self.data().iter() {
+            let _ = closure.add_node( id.clone(), node.data().clone() );
+        }
+        for ( from, row ) in ids.iter().zip( reachable.iter() ) {
+            for ( to, _ ) in ids.iter().zip( row.iter() ).filter( |( _, &reaches )| reaches ) {
+                let _ = closure.add_edge( from.clone(), to.clone(), () );
+            }
+        }
+        closure
+    }
+
+    // Smallest subgraph with the same reachability, only unique for acyclic graphs. Children of every
+    // node are visited in topological order and kept unless an earlier kept child already reaches them.
+    pub fn transitive_reduction( &self ) -> Result<DiGraph<I, N, E>, Cycle<I>>
+    where
+        N: Clone + PartialEq,
+        E: Clone + PartialEq
+    {
+        let order = self.toposort()?;
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let reachable = reachable( &adjacency );
+        let mut position = vec![ 0; order.len() ];
+        for ( i, id ) in order.iter().enumerate() {
+            if let Some( v ) = indexed.position( id ) {
+                position[ v ] = i;
+            }
+        }
+
+        let mut reduction = DiGraph::new();
+        for ( id, node ) in self.data().iter() {
+            let _ = reduction.add_node( id.clone(), node.data().clone() );
+        }
+        for ( u, edges ) in indexed.adjacency.iter().enumerate() {
+            let mut children = edges.clone();
+            children.sort_by_key( |&( v, _ )| position[ v ] );
+            let mut covered = vec![ false; adjacency.len() ];
+            for ( v, edge ) in children {
+                if covered[ v ] {
+                    continue;
+                }
+                covered.iter_mut().zip( reachable[ v ].iter() ).for_each( |( a, &b )| *a |= b );
+                let _ = reduction.add_edge( indexed.ids[ u ].clone(), indexed.ids[ v ].clone(), edge.clone() );
+            }
+        }
+        Ok( reduction )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{ Cycle, GraphTraits },
+        directed_graph::DiGraph
+    };
+
+    // 1 -> 2 -> 3 -> 4 with the shortcuts 1 -> 3, 1 -> 4 and 2 -> 4, plus the isolated node 5.
+    fn graph() -> DiGraph<usize, (), char> {
+        let mut graph = DiGraph::<usize, (), char>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, label ) in [ ( 1, 2, 'a' ), ( 2, 3, 'b' ), ( 3, 4, 'c' ), ( 1, 3, 'x' ), ( 1, 4, 'y' ), ( 2, 4, 'z' ) ] {
+            graph.add_edge( u, v, label ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let mut graph = graph();
+        graph.delete_edge( 1, 4 ).unwrap();
+        let closure = graph.transitive_closure();
+        assert_eq!( closure.order(), 5 );
+        assert!( closure.contains_edge( 1, 4 ) );
+        assert!( !closure.contains_edge( 4, 1 ) );
+        assert!( !closure.contains_edge( 1, 1 ) );
+
+        graph.add_edge( 4, 2, 'w' ).unwrap();
+        let closure = graph.transitive_closure();
+        for id in 2..=4 {
+            assert!( closure.contains_edge( id, id ) );
+        }
+        assert!( !closure.contains_edge( 1, 1 ) );
+        assert!( closure.contains_edge( 3, 2 ) );
+    }
+
+    #[test]
+    fn test_reachability_matrix() {
+        let reachability = graph().reachability_matrix();
+        assert_eq!( reachability.ids(), &[ 1, 2, 3, 4, 5 ] );
+        assert!( reachability.reaches( &1, &4 ) );
+        assert!( !reachability.reaches( &4, &1 ) );
+        assert!( !reachability.reaches( &5, &5 ) );
+        assert!( !reachability.reaches( &1, &9 ) );
+        assert_eq!( reachability.matrix()[ 1 ], vec![ false, false, true, true, false ] );
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let reduction = graph().transitive_reduction().unwrap();
+        assert_eq!( reduction.order(), 5 );
+        assert_eq!( reduction.get_edge( 1, 2 ), Some( &'a' ) );
+        assert_eq!( reduction.get_edge( 2, 3 ), Some( &'b' ) );
+        assert_eq!( reduction.get_edge( 3, 4 ), Some( &'c' ) );
+        assert!( !reduction.contains_edge( 1, 3 ) );
+        assert!( !reduction.contains_edge( 1, 4 ) );
+        assert!( !reduction.contains_edge( 2, 4 ) );
+
+        let mut cyclic = graph();
+        cyclic.add_edge( 4, 1, 'w' ).unwrap();
+        assert!( matches!( cyclic.transitive_reduction(), Err( Cycle( _ ) ) ) );
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::BTreeMap;
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    directed_graph::DiGraph
+};
+
+// Rooted forest on 0..n, edges point from parents to children.
+struct Forest {
+    parent: Vec<Option<usize>>,
+    root: Vec<usize>,
+    depth: Vec<usize>,
+    preorder: Vec<usize>
+}
+
+// None unless every node has at most one parent and every node can be reached from a root.
+fn forest( adjacency: &[Vec<usize>] ) -> Option<Forest> {
+    let n = adjacency.len();
+    let mut parent = vec![ None; n ];
+    for ( v, children ) in adjacency.iter().enumerate() {
+        for &w in children.iter() {
+            if parent[ w ].replace( v ).is_some() {
+                return None;
+            }
+        }
+    }
+    let mut root = vec![ usize::MAX; n ];
+    let mut depth = vec![ 0; n ];
+    let mut preorder = Vec::with_capacity( n );
+    for start in ( 0..n ).filter( |&v| parent[ v ].is_none() ) {
+        let mut stack = vec![ start ];
+        while let Some( v ) = stack.pop() {
+            root[ v ] = start;
+            preorder.push( v );
+            for &w in adjacency[ v ].iter().rev() {
+                depth[ w ] = depth[ v ] + 1;
+                stack.push( w );
+            }
+        }
+    }
+    // Nodes left over lie on a cycle or below one.
+    ( preorder.len() == n ).then_some( Forest { parent, root, depth, preorder } )
+}
+
+impl Forest {
+    // Heights and subtree sizes, children are finished before their parents in reverse preorder.
+    fn heights_and_sizes( &self ) -> ( Vec<usize>, Vec<usize> ) {
+        let n = self.parent.len();
+        let mut height = vec![ 0; n ];
+        let mut size = vec![ 1; n ];
+        for &v in self.preorder.iter().rev() {
+            if let Some( p ) = self.parent[ v ] {
+                height[ p ] = height[ p ].max( height[ v ] + 1 );
+                size[ p ] += size[ v ];
+            }
+        }
+        ( height, size )
+    }
+}
+
+// Lowest common ancestors in a rooted forest, an Euler tour with a sparse table of shallowest nodes
+// answers every query in constant time.
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct LowestCommonAncestors<I> {
+    ids: Vec<I>,
+    root: Vec<usize>,
+    depth: Vec<usize>,
+    first: Vec<usize>,
+    table: Vec<Vec<usize>>
+}
+
+impl<I> LowestCommonAncestors<I>
+where
+    I: Clone + Ord
+{
+    fn new( ids: Vec<I>, adjacency: &[Vec<usize>], forest: Forest ) -> Self {
+        let n = ids.len();
+        let mut tour = Vec::with_capacity( 2 * n );
+        let mut first = vec![ 0; n ];
+        for start in ( 0..n ).filter( |&v| forest.parent[ v ].is_none() ) {
+            let mut frames = vec![ ( start, 0 ) ];
+            first[ start ] = tour.len();
+            tour.push( start );
+            while let Some( frame ) = frames.last_mut() {
+                let v = frame.0;
+                if let Some( &w ) = adjacency[ v ].get( frame.1 ) {
+                    frame.1 += 1;
+                    first[ w ] = tour.len();
+                    tour.push( w );
+                    frames.push( ( w, 0 ) );
+                } else {
+                    frames.pop();
+                    if let Some( &( p, _ ) ) = frames.last() {
+                        tour.push( p );
+                    }
+                }
+            }
+        }
+
+        let depth = forest.depth;
+        let mut table = vec![ tour ];
+        let mut span = 1;
+        while 2 * span <= table[ 0 ].len() {
+            let last = &table[ table.len() - 1 ];
+            let row = ( 0..last.len() - span )
+                .map( |i| {
+                    let ( a, b ) = ( last[ i ], last[ i + span ] );
+                    if depth[ b ] < depth[ a ] { b } else { a }
+                } )
+                .collect();
+            table.push( row );
+            span *= 2;
+        }
+        Self { ids, root: forest.root, depth, first, table }
+    }
+
+    fn shallowest( &self, a: usize, b: usize ) -> usize {
+        let ( from, to ) = ( self.first[ a ].min( self.first[ b ] ), self.first[ a ].max( self.first[ b ] ) );
+        let level = ( to - from + 1 ).ilog2() as usize;
+        let ( x, y ) = ( self.table[ level ][ from ], self.table[ level ][ to + 1 - ( 1 << level ) ] );
+        if self.depth[ y ] < self.depth[ x ] { y } else { x }
+    }
+
+    fn positions( &self, a: &I, b: &I ) -> Option<( usize, usize )> {
+        let ( a, b ) = ( self.ids.binary_search( a ).ok()?, self.ids.binary_search( b ).ok()? );
+        ( self.root[ a ] == self.root[ b ] ).then_some( ( a, b ) )
+    }
+
+    // None when either node is missing or the two lie in different trees.
+    pub fn lca( &self, a: &I, b: &I ) -> Option<I> {
+        let ( a, b ) = self.positions( a, b )?;
+        Some( self.ids[ self.shallowest( a, b ) ].clone() )
+    }
+
+    // Number of edges on the tree path between `a` and `b`.
+    pub fn distance( &self, a: &I, b: &I ) -> Option<usize> {
+        let ( a, b ) = self.positions( a, b )?;
+        Some( self.depth[ a ] + self.depth[ b ] - 2 * self.depth[ self.shallowest( a, b ) ] )
+    }
+
+    pub fn depth( &self, node: &I ) -> Option<usize> {
+        self.ids.binary_search( node ).ok().map( |v| self.depth[ v ] )
+    }
+}
+
+impl<I, N, E> DiGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn forest( &self ) -> Option<( Vec<I>, Forest )> {
+        let indexed = Indexed::new( self.data() );
+        let forest = forest( &indexed.neighbors() )?;
+        Some( ( indexed.ids, forest ) )
+    }
+
+    fn per_node( ids: Vec<I>, values: Vec<usize> ) -> BTreeMap<I, usize> {
+        ids.into_iter().zip( values ).collect()
+    }
+
+    // Every node has at most one parent and there are no cycles. The null graph is an empty forest.
+    pub fn is_forest( &self ) -> bool {
+        self.forest().is_some()
+    }
+
+    // A forest with exactly one root, every node can be reached from it.
+    pub fn is_tree( &self ) -> bool {
+        self.forest().is_some_and( |( _, forest )| forest.parent.iter().filter( |parent| parent.is_none() ).count() == 1 )
+    }
+
+    // Follows parents upwards, None when the node is missing or some ancestor has several parents or
+    // lies on a cycle.
+    pub fn root_of( &self, node: I ) -> Option<I> {
+        let indexed = Indexed::new( self.data() );
+        let mut parents = vec![ Vec::new(); indexed.ids.len() ];
+        for ( v, children ) in indexed.neighbors().into_iter().enumerate() {
+            children.into_iter().for_each( |w| parents[ w ].push( v ) );
+        }
+        let mut current = indexed.position( &node )?;
+        for _ in 0..=indexed.ids.len() {
+            match parents[ current ].as_slice() {
+                [] => return Some( indexed.ids[ current ].clone() ),
+                [ parent ] => current = *parent,
+                _ => return None
+            }
+        }
+        None
+    }
+
+    // Number of edges from the root of its tree to every node, None unless the graph is a forest.
+    pub fn depths( &self ) -> Option<BTreeMap<I, usize>> {
+        let ( ids, forest ) = self.forest()?;
+        Some( Self::per_node( ids, forest.depth ) )
+    }
+
+    // Longest path down to a leaf from every node, None unless the graph is a forest.
+    pub fn heights( &self ) -> Option<BTreeMap<I, usize>> {
+        let ( ids, forest ) = self.forest()?;
+        Some( Self::per_node( ids, forest.heights_and_sizes().0 ) )
+    }
+
+    // Number of nodes below every node, the node itself included. None unless the graph is a forest.
+    pub fn subtree_sizes( &self ) -> Option<BTreeMap<I, usize>> {
+        let ( ids, forest ) = self.forest()?;
+        Some( Self::per_node( ids, forest.heights_and_sizes().1 ) )
+    }
+
+    // None unless the graph is a forest.
+    pub fn lowest_common_ancestors( &self ) -> Option<LowestCommonAncestors<I>> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let forest = forest( &adjacency )?;
+        Some( LowestCommonAncestors::new( indexed.ids, &adjacency, forest ) )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::GraphTraits,
+        directed_graph::DiGraph
+    };
+
+    //       1
+    //     / | \
+    //    2  3  4
+    //   / \     \
+    //  5   6     7
+    //            |
+    //            8
+    fn tree() -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=8 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 1, 3 ), ( 1, 4 ), ( 2, 5 ), ( 2, 6 ), ( 4, 7 ), ( 7, 8 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_is_tree() {
+        let mut graph = tree();
+        assert!( graph.is_tree() );
+        assert!( graph.is_forest() );
+        assert_eq!( graph.root_of( 8 ), Some( 1 ) );
+        assert_eq!( graph.root_of( 1 ), Some( 1 ) );
+        assert_eq!( graph.root_of( 9 ), None );
+
+        graph.add_node( 9, () ).unwrap();
+        assert!( !graph.is_tree() );
+        assert!( graph.is_forest() );
+        assert_eq!( graph.root_of( 9 ), Some( 9 ) );
+
+        graph.add_edge( 9, 6, () ).unwrap();
+        assert!( !graph.is_forest() );
+        assert_eq!( graph.root_of( 6 ), None );
+        assert_eq!( graph.root_of( 5 ), Some( 1 ) );
+
+        let mut cyclic = tree();
+        cyclic.add_edge( 8, 4, () ).unwrap();
+        cyclic.delete_edge( 1, 4 ).unwrap();
+        assert!( !cyclic.is_forest() );
+        assert_eq!( cyclic.root_of( 8 ), None );
+        assert!( DiGraph::<usize, (), ()>::new().is_forest() );
+        assert!( !DiGraph::<usize, (), ()>::new().is_tree() );
+    }
+
+    #[test]
+    fn test_depth_height_size() {
+        let graph = tree();
+        let depths = graph.depths().unwrap();
+        assert_eq!( depths[ &1 ], 0 );
+        assert_eq!( depths[ &6 ], 2 );
+        assert_eq!( depths[ &8 ], 3 );
+        let heights = graph.heights().unwrap();
+        assert_eq!( heights[ &1 ], 3 );
+        assert_eq!( heights[ &2 ], 1 );
+        assert_eq!( heights[ &3 ], 0 );
+        let sizes = graph.subtree_sizes().unwrap();
+        assert_eq!( sizes[ &1 ], 8 );
+        assert_eq!( sizes[ &2 ], 3 );
+        assert_eq!( sizes[ &4 ], 3 );
+
+        let mut cyclic = tree();
+        cyclic.add_edge( 8, 1, () ).unwrap();
+        assert_eq!( cyclic.depths(), None );
+    }
+
+    #[test]
+    fn test_lowest_common_ancestors() {
+        let mut graph = tree();
+        graph.add_node( 9, () ).unwrap();
+        graph.add_node( 10, () ).unwrap();
+        graph.add_edge( 9, 10, () ).unwrap();
+        let lca = graph.lowest_common_ancestors().unwrap();
+        assert_eq!( lca.lca( &5, &6 ), Some( 2 ) );
+        assert_eq!( lca.lca( &5, &8 ), Some( 1 ) );
+        assert_eq!( lca.lca( &7, &8 ), Some( 7 ) );
+        assert_eq!( lca.lca( &8, &7 ), Some( 7 ) );
+        assert_eq!( lca.lca( &3, &3 ), Some( 3 ) );
+        assert_eq!( lca.lca( &10, &9 ), Some( 9 ) );
+        assert_eq!( lca.lca( &5, &10 ), None );
+        assert_eq!( lca.lca( &5, &11 ), None );
+        assert_eq!( lca.distance( &6, &8 ), Some( 5 ) );
+        assert_eq!( lca.depth( &8 ), Some( 3 ) );
+    }
+}
@@ -3,6 +3,16 @@
 #![warn(private_bounds)]
 
 pub mod traverser;
+pub mod weight;
+pub mod shortest_path;
+pub mod all_pairs;
+pub mod hamiltonian;
+pub mod k_shortest;
+pub mod metrics;
+pub mod visit;
+pub(crate) mod indexed;
+#[cfg(test)]
+pub(crate) mod fixtures;
 
 //: Standard
 use std::{
@@ -26,6 +36,26 @@ pub enum Error {
     EdgeAlreadyExists,
 }
 
+// Closed walk v0 -> v1 -> ... -> vk -> v0, the first node is not repeated at the end.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Graph Error: Cycle Detected")]
+pub struct Cycle<I>( pub Vec<I> );
+
+// Same layout as `Cycle`, the weights along the cycle sum to a negative value.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Graph Error: Negative Cycle Detected")]
+pub struct NegativeCycle<I>( pub Vec<I> );
+
+// A bounded search gave up before it could decide either way.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Graph Error: Expansion Limit Reached")]
+pub struct ExpansionLimit;
+
+// An exact search was asked to pair up more odd degree nodes than it supports, holds their count.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Graph Error: Too Many Odd Nodes")]
+pub struct TooManyOddNodes( pub usize );
+
 pub type AdjacencyData<I, E> = BTreeMap<I, E>;
 
 #[derive( Debug, Clone, PartialEq, Eq )]
@@ -0,0 +1,159 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::ops::Sub;
+
+use crate::graph::{
+    GraphData,
+    NegativeCycle,
+    indexed::Indexed,
+    shortest_path::bellman_ford,
+    weight::Weight
+};
+
+// Row `i` of `predecessors` is the shortest-path tree rooted at `ids[ i ]`, `usize::MAX` marks a missing entry.
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct DistanceMatrix<I, W> {
+    ids: Vec<I>,
+    distances: Vec<Vec<Option<W>>>,
+    predecessors: Vec<Vec<usize>>
+}
+
+impl<I, W> DistanceMatrix<I, W>
+where
+    I: Clone + Ord
+{
+    pub fn ids( &self ) -> &[I] {
+        &self.ids
+    }
+
+    pub fn distance( &self, from: &I, to: &I ) -> Option<&W> {
+        let from = self.ids.binary_search( from ).ok()?;
+        let to = self.ids.binary_search( to ).ok()?;
+        self.distances[ from ][ to ].as_ref()
+    }
+
+    pub fn path( &self, from: &I, to: &I ) -> Option<Vec<I>> {
+        let from = self.ids.binary_search( from ).ok()?;
+        let mut current = self.ids.binary_search( to ).ok()?;
+        self.distances[ from ][ current ].as_ref()?;
+        let mut path = vec![ self.ids[ current ].clone() ];
+        while current != from {
+            current = self.predecessors[ from ][ current ];
+            if current == usize::MAX || path.len() > self.ids.len() {
+                return None;
+            }
+            path.push( self.ids[ current ].clone() );
+        }
+        path.reverse();
+        Some( path )
+    }
+}
+
+impl<I, N, E> GraphData<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn all_pairs_negative_cycle<W>( indexed: &Indexed<'_, I, E>, adjacency: &[Vec<( usize, W )>] ) -> NegativeCycle<I>
+    where
+        W: Weight
+    {
+        let sources: Vec<usize> = ( 0..adjacency.len() ).collect();
+        let cycle = bellman_ford( adjacency, &sources ).err().expect( "a negative distance from a node to itself means a negative cycle" );
+        NegativeCycle( cycle.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() )
+    }
+
+    pub(crate) fn floyd_warshall<W, F>( &self, weight: F ) -> Result<DistanceMatrix<I, W>, NegativeCycle<I>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self );
+        let adjacency = indexed.weighted( weight );
+        let n = adjacency.len();
+        let mut distances: Vec<Vec<Option<W>>> = ( 0..n ).map( |_| ( 0..n ).map( |_| None ).collect() ).collect();
+        let mut predecessors = vec![ vec![ usize::MAX; n ]; n ];
+
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            distances[ u ][ u ] = Some( W::zero() );
+            for ( v, weight ) in edges.iter() {
+                if distances[ u ][ *v ].as_ref().is_none_or( |best| weight < best ) {
+                    distances[ u ][ *v ] = Some( weight.clone() );
+                    predecessors[ u ][ *v ] = u;
+                }
+            }
+        }
+
+        // Stop at the first negative cycle, past it the distances shrink geometrically and overflow.
+        let negative = |distances: &[Vec<Option<W>>]| ( 0..n ).any( |i| distances[ i ][ i ].as_ref().is_some_and( |distance| *distance < W::zero() ) );
+        for k in 0..n {
+            if negative( &distances ) {
+                return Err( Self::all_pairs_negative_cycle( &indexed, &adjacency ) );
+            }
+            for i in 0..n {
+                let Some( to_k ) = distances[ i ][ k ].clone() else {
+                    continue;
+                };
+                for j in 0..n {
+                    let Some( from_k ) = distances[ k ][ j ].clone() else {
+                        continue;
+                    };
+                    let through = to_k.clone() + from_k;
+                    if distances[ i ][ j ].as_ref().is_none_or( |best| through < *best ) {
+                        distances[ i ][ j ] = Some( through );
+                        predecessors[ i ][ j ] = predecessors[ k ][ j ];
+                    }
+                }
+            }
+        }
+
+        if negative( &distances ) {
+            return Err( Self::all_pairs_negative_cycle( &indexed, &adjacency ) );
+        }
+        Ok( DistanceMatrix { ids: indexed.ids, distances, predecessors } )
+    }
+
+    // Bellman–Ford from a virtual source yields potentials that make every edge non-negative,
+    // after which Dijkstra runs from each node on the reweighted graph.
+    pub(crate) fn johnson<W, F>( &self, weight: F ) -> Result<DistanceMatrix<I, W>, NegativeCycle<I>>
+    where
+        W: Weight + Sub<Output = W>,
+        F: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self );
+        let adjacency = indexed.weighted( &weight );
+        let n = adjacency.len();
+        let sources: Vec<usize> = ( 0..n ).collect();
+        let potentials: Vec<W> = match bellman_ford( &adjacency, &sources ) {
+            Ok( relaxed ) => relaxed.distances.into_iter().map( |distance| distance.unwrap_or( W::zero() ) ).collect(),
+            Err( _ ) => return Err( Self::all_pairs_negative_cycle( &indexed, &adjacency ) )
+        };
+        let potential = |id: &I| potentials[ indexed.position( id ).expect( "Dijkstra only follows edges between nodes of the graph" ) ].clone();
+
+        let mut distances = Vec::with_capacity( n );
+        let mut predecessors = Vec::with_capacity( n );
+        for ( i, source ) in indexed.ids.iter().enumerate() {
+            let ( paths, _ ) = self.dijkstra(
+                source.clone(),
+                // Non-negative in exact arithmetic, the clamp keeps float rounding from dropping the edge.
+                |from, to, edge| Some( ( weight( edge ) + potential( from ) - potential( to ) ).max( W::zero() ) ),
+                |_| false
+            );
+            let mut row: Vec<Option<W>> = ( 0..n ).map( |_| None ).collect();
+            let mut tree = vec![ usize::MAX; n ];
+            for ( id, distance ) in paths.distances().iter() {
+                if let Some( j ) = indexed.position( id ) {
+                    row[ j ] = Some( distance.clone() + potentials[ j ].clone() - potentials[ i ].clone() );
+                }
+            }
+            for ( id, predecessor ) in paths.predecessors().iter() {
+                if let ( Some( j ), Some( p ) ) = ( indexed.position( id ), indexed.position( predecessor ) ) {
+                    tree[ j ] = p;
+                }
+            }
+            distances.push( row );
+            predecessors.push( tree );
+        }
+        Ok( DistanceMatrix { ids: indexed.ids, distances, predecessors } )
+    }
+}
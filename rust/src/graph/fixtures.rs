@@ -0,0 +1,53 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{ Error, GraphTraits },
+    directed_graph::DiGraph,
+    undirected_graph::UnGraph
+};
+
+// Test graphs on the nodes `0..n`. Repeated edges are skipped, edges to missing nodes panic.
+
+pub fn digraph( n: usize, edges: &[ ( usize, usize ) ] ) -> DiGraph<usize, (), ()> {
+    weighted_digraph( n, &edges.iter().map( |&( u, v )| ( u, v, () ) ).collect::<Vec<_>>() )
+}
+
+pub fn weighted_digraph<W>( n: usize, edges: &[ ( usize, usize, W ) ] ) -> DiGraph<usize, (), W>
+where
+    W: Clone + PartialEq
+{
+    let mut graph = DiGraph::<usize, (), W>::new();
+    for id in 0..n {
+        graph.add_node( id, () ).unwrap();
+    }
+    for ( u, v, weight ) in edges.iter() {
+        assert!( matches!( graph.add_edge( *u, *v, weight.clone() ), Ok( () ) | Err( Error::EdgeAlreadyExists ) ) );
+    }
+    graph
+}
+
+pub fn ungraph( n: usize, edges: &[ ( usize, usize ) ] ) -> UnGraph<usize, (), ()> {
+    weighted_ungraph( n, &edges.iter().map( |&( u, v )| ( u, v, () ) ).collect::<Vec<_>>() )
+}
+
+pub fn weighted_ungraph<W>( n: usize, edges: &[ ( usize, usize, W ) ] ) -> UnGraph<usize, (), W>
+where
+    W: Clone + PartialEq
+{
+    let mut graph = UnGraph::<usize, (), W>::new();
+    for id in 0..n {
+        graph.add_node( id, () ).unwrap();
+    }
+    for ( u, v, weight ) in edges.iter() {
+        assert!( matches!( graph.add_edge( *u, *v, weight.clone() ), Ok( () ) | Err( Error::EdgeAlreadyExists ) ) );
+    }
+    graph
+}
+
+// Outer five cycle 0..5, spokes i - i + 5 and the inner pentagram on 5..10.
+pub fn petersen() -> UnGraph<usize, (), ()> {
+    let edges: Vec<( usize, usize )> = ( 0..5 )
+        .flat_map( |i| [ ( i, ( i + 1 ) % 5 ), ( i, i + 5 ), ( i + 5, ( i + 2 ) % 5 + 5 ) ] )
+        .collect();
+    ungraph( 10, &edges )
+}
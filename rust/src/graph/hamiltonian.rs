@@ -0,0 +1,285 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::graph::{
+    ExpansionLimit,
+    Graph,
+    GraphAccess,
+    GraphType,
+    indexed::Indexed
+};
+
+// Largest graph that is solved exactly with the bitmask DP, 2^20 masks of u32 end sets.
+pub const HELD_KARP_NODES: usize = 20;
+
+fn bits( mut set: u32 ) -> impl Iterator<Item = usize> {
+    std::iter::from_fn( move || ( set != 0 ).then( || {
+        let bit = set.trailing_zeros() as usize;
+        set &= set - 1;
+        bit
+    } ) )
+}
+
+// Held–Karp over reachability only. `ends[ mask ]` holds every node a path covering `mask` can end on.
+// Cycles are rooted at node 0.
+pub(crate) fn held_karp( adjacency: &[Vec<usize>], closed: bool ) -> Option<Vec<usize>> {
+    let n = adjacency.len();
+    let out: Vec<u32> = adjacency.iter().map( |edges| edges.iter().fold( 0, |set, &w| set | 1 << w ) ).collect();
+    let mut into = vec![ 0u32; n ];
+    for ( v, &set ) in out.iter().enumerate() {
+        bits( set ).for_each( |w| into[ w ] |= 1 << v );
+    }
+
+    let full = ( 1usize << n ) - 1;
+    let mut ends = vec![ 0u32; 1 << n ];
+    if closed {
+        ends[ 1 ] = 1;
+    } else {
+        ( 0..n ).for_each( |v| ends[ 1 << v ] = 1 << v );
+    }
+    for mask in 1..full {
+        for v in bits( ends[ mask ] ) {
+            for w in bits( out[ v ] & !( mask as u32 ) ) {
+                ends[ mask | 1 << w ] |= 1 << w;
+            }
+        }
+    }
+
+    let last = if closed { ends[ full ] & into[ 0 ] } else { ends[ full ] };
+    let mut v = bits( last ).next()?;
+    let mut mask = full;
+    let mut path = vec![ v ];
+    while mask != 1 << v {
+        mask &= !( 1 << v );
+        v = bits( ends[ mask ] & into[ v ] ).next()?;
+        path.push( v );
+    }
+    path.reverse();
+    Some( path )
+}
+
+// Depth first search that tries the neighbour with the fewest open neighbours first and backs off
+// as soon as the unvisited nodes can no longer all be reached from the end of the path.
+pub(crate) struct Backtrack<'a> {
+    adjacency: &'a [Vec<usize>],
+    closed: bool,
+    limit: Option<usize>,
+    expanded: usize,
+    visited: Vec<bool>
+}
+
+impl<'a> Backtrack<'a> {
+    pub fn new( adjacency: &'a [Vec<usize>], closed: bool, limit: Option<usize> ) -> Self {
+        Self { adjacency, closed, limit, expanded: 0, visited: vec![ false; adjacency.len() ] }
+    }
+
+    fn open( &self, v: usize ) -> usize {
+        self.adjacency[ v ].iter().filter( |&&w| !self.visited[ w ] ).count()
+    }
+
+    fn candidates( &self, v: usize ) -> Vec<usize> {
+        let mut candidates: Vec<usize> = self.adjacency[ v ].iter().copied().filter( |&w| !self.visited[ w ] ).collect();
+        candidates.sort_by_key( |&w| self.open( w ) );
+        candidates
+    }
+
+    // Every unvisited node has to be reachable from `v` through unvisited nodes, and a cycle also
+    // needs a way back to `start`.
+    fn viable( &self, v: usize, start: usize, remaining: usize ) -> bool {
+        let mut seen = vec![ false; self.adjacency.len() ];
+        let mut stack = vec![ v ];
+        let mut reached = 0;
+        let mut returns = false;
+        while let Some( u ) = stack.pop() {
+            for &w in self.adjacency[ u ].iter() {
+                returns |= w == start;
+                if !self.visited[ w ] && !seen[ w ] {
+                    seen[ w ] = true;
+                    reached += 1;
+                    stack.push( w );
+                }
+            }
+        }
+        reached == remaining && ( !self.closed || returns )
+    }
+
+    pub fn search( &mut self, start: usize ) -> Result<Option<Vec<usize>>, ExpansionLimit> {
+        let n = self.adjacency.len();
+        let mut path = vec![ start ];
+        self.visited[ start ] = true;
+        if n == 1 {
+            return Ok( ( !self.closed ).then_some( path ) );
+        }
+        let mut stack = vec![ ( self.candidates( start ), 0 ) ];
+
+        while let Some( ( candidates, next ) ) = stack.last_mut() {
+            let Some( &w ) = candidates.get( *next ) else {
+                stack.pop();
+                if let Some( v ) = path.pop() {
+                    self.visited[ v ] = false;
+                }
+                continue;
+            };
+            *next += 1;
+            self.expanded += 1;
+            if self.limit.is_some_and( |limit| self.expanded > limit ) {
+                return Err( ExpansionLimit );
+            }
+
+            self.visited[ w ] = true;
+            path.push( w );
+            if path.len() == n {
+                if !self.closed || self.adjacency[ w ].contains( &start ) {
+                    return Ok( Some( path ) );
+                }
+            } else if self.viable( w, start, n - path.len() ) {
+                stack.push( ( self.candidates( w ), 0 ) );
+                continue;
+            }
+            path.pop();
+            self.visited[ w ] = false;
+        }
+        Ok( None )
+    }
+}
+
+impl<T, I, N, E> Graph<T, I, N, E>
+where
+    T: GraphType,
+    I: Clone + Ord
+{
+    fn hamiltonian( &self, closed: bool, expansion_limit: Option<usize> ) -> Result<Option<Vec<I>>, ExpansionLimit> {
+        let indexed = Indexed::new( self.data() );
+        let mut adjacency = indexed.neighbors();
+        for ( v, edges ) in adjacency.iter_mut().enumerate() {
+            edges.retain( |&w| w != v );
+        }
+        let n = adjacency.len();
+        if n == 0 || ( closed && n < 3 ) {
+            return Ok( None );
+        }
+
+        let path = if n <= HELD_KARP_NODES {
+            held_karp( &adjacency, closed )
+        } else {
+            let mut backtrack = Backtrack::new( &adjacency, closed, expansion_limit );
+            let mut has_incoming = vec![ false; n ];
+            adjacency.iter().flatten().for_each( |&w| has_incoming[ w ] = true );
+            // An open path has to start on a node nothing points at, if there is one.
+            let starts: Vec<usize> = match ( closed, ( 0..n ).filter( |&v| !has_incoming[ v ] ).collect::<Vec<_>>() ) {
+                ( true, _ ) => vec![ 0 ],
+                ( false, sources ) if sources.is_empty() => ( 0..n ).collect(),
+                ( false, sources ) if sources.len() == 1 => sources,
+                _ => Vec::new()
+            };
+            let mut found = None;
+            for start in starts {
+                found = backtrack.search( start )?;
+                if found.is_some() {
+                    break;
+                }
+            }
+            found
+        };
+        Ok( path.map( |path| path.into_iter().map( |v| indexed.ids[ v ].clone() ).collect() ) )
+    }
+
+    // Visits every node exactly once. Graphs up to `HELD_KARP_NODES` nodes are solved exactly, larger
+    // ones by backtracking that gives up with `ExpansionLimit` after `expansion_limit` nodes were tried.
+    pub fn hamiltonian_path( &self, expansion_limit: Option<usize> ) -> Result<Option<Vec<I>>, ExpansionLimit> {
+        self.hamiltonian( false, expansion_limit )
+    }
+
+    // Same as `hamiltonian_path` with an edge from the last node back to the first, which is not repeated.
+    // Cycles need at least three nodes so a single undirected edge never counts as one.
+    pub fn hamiltonian_cycle( &self, expansion_limit: Option<usize> ) -> Result<Option<Vec<I>>, ExpansionLimit> {
+        self.hamiltonian( true, expansion_limit )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ Backtrack, held_karp };
+    use crate::{
+        graph::{ ExpansionLimit, GraphTraits, fixtures::petersen },
+        directed_graph::DiGraph,
+        undirected_graph::UnGraph
+    };
+
+    fn grid( size: usize ) -> UnGraph<usize, (), ()> {
+        let mut graph = UnGraph::<usize, (), ()>::new();
+        for id in 0..size * size {
+            graph.add_node( id, () ).unwrap();
+        }
+        for row in 0..size {
+            for column in 0..size {
+                let id = row * size + column;
+                if column + 1 < size {
+                    graph.add_edge( id, id + 1, () ).unwrap();
+                }
+                if row + 1 < size {
+                    graph.add_edge( id, id + size, () ).unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    fn assert_path( graph: &UnGraph<usize, (), ()>, path: &[ usize ] ) {
+        assert_eq!( path.len(), graph.order() );
+        for pair in path.windows( 2 ) {
+            assert!( graph.contains_edge( pair[ 0 ], pair[ 1 ] ) );
+        }
+    }
+
+    #[test]
+    fn test_hamiltonian_held_karp() {
+        let graph = petersen();
+        let path = graph.hamiltonian_path( None ).unwrap().unwrap();
+        assert_path( &graph, &path );
+        assert_eq!( graph.hamiltonian_cycle( None ).unwrap(), None );
+
+        let grid = grid( 4 );
+        let cycle = grid.hamiltonian_cycle( None ).unwrap().unwrap();
+        assert_path( &grid, &cycle );
+        assert!( grid.contains_edge( *cycle.last().unwrap(), cycle[ 0 ] ) );
+    }
+
+    #[test]
+    fn test_hamiltonian_directed() {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 2, 3 ), ( 3, 4 ), ( 2, 4 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        assert_eq!( graph.hamiltonian_path( None ).unwrap(), Some( vec![ 1, 2, 3, 4 ] ) );
+        assert_eq!( graph.hamiltonian_cycle( None ).unwrap(), None );
+        graph.add_edge( 4, 1, () ).unwrap();
+        assert_eq!( graph.hamiltonian_cycle( None ).unwrap(), Some( vec![ 1, 2, 3, 4 ] ) );
+    }
+
+    #[test]
+    fn test_hamiltonian_backtracking() {
+        let graph = grid( 6 );
+        let path = graph.hamiltonian_path( None ).unwrap().unwrap();
+        assert_path( &graph, &path );
+        let cycle = graph.hamiltonian_cycle( None ).unwrap().unwrap();
+        assert_path( &graph, &cycle );
+        assert!( graph.contains_edge( *cycle.last().unwrap(), cycle[ 0 ] ) );
+
+        // An odd grid is bipartite with unequal sides, so the search has to exhaust or give up.
+        assert!( matches!( grid( 5 ).hamiltonian_cycle( Some( 1000 ) ), Err( ExpansionLimit ) ) );
+    }
+
+    #[test]
+    fn test_hamiltonian_agree() {
+        let graph = petersen();
+        let adjacency: Vec<Vec<usize>> = ( 0..10 ).map( |v| graph.edges( v ).unwrap().keys().copied().collect() ).collect();
+        for closed in [ false, true ] {
+            let exact = held_karp( &adjacency, closed );
+            let searched = ( 0..10 ).find_map( |start| Backtrack::new( &adjacency, closed, None ).search( start ).unwrap() );
+            assert_eq!( exact.is_some(), searched.is_some() );
+        }
+    }
+}
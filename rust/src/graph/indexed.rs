@@ -0,0 +1,49 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::BTreeMap;
+
+use crate::graph::GraphData;
+
+// Dense view of a graph, mapping every node id onto 0..n so algorithms can work on plain vectors.
+// Edges pointing at ids that are not nodes of the graph are dropped.
+pub(crate) struct Indexed<'g, I, E> {
+    pub ids: Vec<I>,
+    pub adjacency: Vec<Vec<( usize, &'g E )>>
+}
+
+impl<'g, I, E> Indexed<'g, I, E>
+where
+    I: Clone + Ord
+{
+    pub fn new<N>( data: &'g GraphData<I, N, E> ) -> Self {
+        let ids: Vec<I> = data.keys().cloned().collect();
+        let index: BTreeMap<I, usize> = ids.iter().cloned().enumerate().map( |( i, id )| ( id, i ) ).collect();
+        let adjacency = data.values()
+            .map( |node| node.adjacencies().iter()
+                .filter_map( |( id, edge )| index.get( id ).map( |&j| ( j, edge ) ) )
+                .collect()
+            )
+            .collect();
+        Self { ids, adjacency }
+    }
+
+    pub fn position( &self, id: &I ) -> Option<usize> {
+        self.ids.binary_search( id ).ok()
+    }
+
+    pub fn weighted<W, F>( &self, weight: F ) -> Vec<Vec<( usize, W )>>
+    where
+        F: Fn( &E ) -> W
+    {
+        self.adjacency.iter()
+            .map( |edges| edges.iter().map( |&( j, edge )| ( j, weight( edge ) ) ).collect() )
+            .collect()
+    }
+
+    pub fn neighbors( &self ) -> Vec<Vec<usize>> {
+        self.adjacency.iter()
+            .map( |edges| edges.iter().map( |&( j, _ )| j ).collect() )
+            .collect()
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Reverse,
+    collections::{ BTreeSet, BinaryHeap }
+};
+
+use crate::graph::{
+    GraphData,
+    weight::Weight
+};
+
+// Yen's algorithm, simple paths from `start` to `end` in order of increasing cost. Every path after
+// the first is found by deviating from an earlier one at some spur node, with the edges those paths
+// take from there and the nodes before the spur blocked. Equal costs come out in path order. Weights
+// must be non-negative, paths over edges with negative weights are never produced.
+pub struct KShortestPaths<'g, I, N, E, W, F> {
+    data: &'g GraphData<I, N, E>,
+    weight: F,
+    start: I,
+    end: I,
+    started: bool,
+    found: Vec<Vec<I>>,
+    seen: BTreeSet<Vec<I>>,
+    candidates: BinaryHeap<Reverse<( W, Vec<I> )>>
+}
+
+impl<'g, I, N, E, W, F> KShortestPaths<'g, I, N, E, W, F>
+where
+    I: Clone + Ord,
+    W: Weight,
+    F: Fn( &E ) -> W
+{
+    pub(crate) fn new( data: &'g GraphData<I, N, E>, start: I, end: I, weight: F ) -> Self {
+        Self {
+            data,
+            weight,
+            start,
+            end,
+            started: false,
+            found: Vec::new(),
+            seen: BTreeSet::new(),
+            candidates: BinaryHeap::new()
+        }
+    }
+
+    fn cost( &self, path: &[I] ) -> Option<W> {
+        path.windows( 2 ).try_fold( W::zero(), |total, pair| {
+            self.data.get_edge( pair[ 0 ].clone(), pair[ 1 ].clone() ).map( |edge| total + ( self.weight )( edge ) )
+        } )
+    }
+
+    fn candidate( &mut self, path: Vec<I>, cost: W ) {
+        if self.seen.insert( path.clone() ) {
+            self.candidates.push( Reverse( ( cost, path ) ) );
+        }
+    }
+
+    fn spur( &self, root: &[I], blocked_edges: &BTreeSet<( I, I )> ) -> Option<( Vec<I>, W )> {
+        let ( spur, before ) = root.split_last()?;
+        let blocked_nodes: BTreeSet<&I> = before.iter().collect();
+        let end = self.end.clone();
+        let ( paths, reached ) = self.data.dijkstra(
+            spur.clone(),
+            |from, to, edge| ( !blocked_nodes.contains( to ) && !blocked_edges.contains( &( from.clone(), to.clone() ) ) )
+                .then( || ( self.weight )( edge ) ),
+            |id| *id == end
+        );
+        let reached = reached?;
+        Some( ( paths.path_to( &reached )?, paths.distance( &reached )?.clone() ) )
+    }
+
+    fn deviate( &mut self, last: &[I] ) {
+        for i in 0..last.len().saturating_sub( 1 ) {
+            let root = &last[ ..=i ];
+            let blocked_edges: BTreeSet<( I, I )> = self.found.iter()
+                .filter( |path| path.len() > i + 1 && path[ ..=i ] == *root )
+                .map( |path| ( path[ i ].clone(), path[ i + 1 ].clone() ) )
+                .collect();
+            let Some( ( spur, spur_cost ) ) = self.spur( root, &blocked_edges ) else {
+                continue;
+            };
+            let Some( root_cost ) = self.cost( root ) else {
+                continue;
+            };
+            let mut path = root[ ..i ].to_vec();
+            path.extend( spur );
+            self.candidate( path, root_cost + spur_cost );
+        }
+    }
+}
+
+impl<I, N, E, W, F> Iterator for KShortestPaths<'_, I, N, E, W, F>
+where
+    I: Clone + Ord,
+    W: Weight,
+    F: Fn( &E ) -> W
+{
+    type Item = ( Vec<I>, W );
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if let Some( ( path, cost ) ) = self.spur( std::slice::from_ref( &self.start ), &BTreeSet::new() ) {
+                self.candidate( path, cost );
+            }
+        } else if let Some( last ) = self.found.last().cloned() {
+            self.deviate( &last );
+        }
+        let Reverse( ( cost, path ) ) = self.candidates.pop()?;
+        self.found.push( path.clone() );
+        Some( ( path, cost ) )
+    }
+}
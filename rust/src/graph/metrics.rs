@@ -0,0 +1,215 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::{ BTreeMap, BTreeSet };
+
+use crate::graph::{
+    Graph,
+    GraphAccess,
+    GraphType,
+    weight::Weight
+};
+
+// Path length that may be infinite, every finite distance orders before `Infinite`.
+#[derive( Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash )]
+pub enum Distance<W> {
+    Finite( W ),
+    Infinite
+}
+
+impl<W> Distance<W> {
+    pub fn finite( &self ) -> Option<&W> {
+        match self {
+            Distance::Finite( distance ) => Some( distance ),
+            Distance::Infinite => None
+        }
+    }
+
+    pub fn is_infinite( &self ) -> bool {
+        matches!( self, Distance::Infinite )
+    }
+}
+
+impl<W> From<Option<W>> for Distance<W> {
+    fn from( distance: Option<W> ) -> Self {
+        distance.map_or( Distance::Infinite, Distance::Finite )
+    }
+}
+
+impl<T, I, N, E> Graph<T, I, N, E>
+where
+    T: GraphType,
+    I: Clone + Ord
+{
+    pub fn eccentricity( &self ) -> BTreeMap<I, Distance<usize>> {
+        self.eccentricity_by( |_| 1usize )
+    }
+
+    // Largest distance from each node to any other, infinite as soon as one node is out of reach.
+    // Directed graphs follow edges forwards. Weights must be non-negative, edges with negative
+    // weights are skipped.
+    pub fn eccentricity_by<W, F>( &self, weight: F ) -> BTreeMap<I, Distance<W>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.data().keys()
+            .map( |id| {
+                let paths = self.data().dijkstra( id.clone(), |_, _, edge| Some( weight( edge ) ), |_| false ).0;
+                let eccentricity = if paths.distances().len() < self.data().len() {
+                    Distance::Infinite
+                } else {
+                    Distance::from( paths.distances().values().max().cloned() )
+                };
+                ( id.clone(), eccentricity )
+            } )
+            .collect()
+    }
+
+    pub fn diameter( &self ) -> Option<Distance<usize>> {
+        self.diameter_by( |_| 1usize )
+    }
+
+    // None for the null graph. Edges with negative weights are skipped as in `eccentricity_by`.
+    pub fn diameter_by<W, F>( &self, weight: F ) -> Option<Distance<W>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.eccentricity_by( weight ).into_values().max()
+    }
+
+    pub fn radius( &self ) -> Option<Distance<usize>> {
+        self.radius_by( |_| 1usize )
+    }
+
+    // None for the null graph. Edges with negative weights are skipped as in `eccentricity_by`.
+    pub fn radius_by<W, F>( &self, weight: F ) -> Option<Distance<W>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.eccentricity_by( weight ).into_values().min()
+    }
+
+    fn extremes<W>( eccentricity: BTreeMap<I, Distance<W>>, target: Option<Distance<W>> ) -> BTreeSet<I>
+    where
+        W: Ord
+    {
+        eccentricity.into_iter()
+            .filter( |( _, distance )| Some( distance ) == target.as_ref() )
+            .map( |( id, _ )| id )
+            .collect()
+    }
+
+    pub fn center( &self ) -> BTreeSet<I> {
+        self.center_by( |_| 1usize )
+    }
+
+    // Nodes whose eccentricity equals the radius, edges with negative weights are skipped.
+    pub fn center_by<W, F>( &self, weight: F ) -> BTreeSet<I>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let eccentricity = self.eccentricity_by( weight );
+        let radius = eccentricity.values().min().cloned();
+        Self::extremes( eccentricity, radius )
+    }
+
+    pub fn periphery( &self ) -> BTreeSet<I> {
+        self.periphery_by( |_| 1usize )
+    }
+
+    // Nodes whose eccentricity equals the diameter, edges with negative weights are skipped.
+    pub fn periphery_by<W, F>( &self, weight: F ) -> BTreeSet<I>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let eccentricity = self.eccentricity_by( weight );
+        let diameter = eccentricity.values().max().cloned();
+        Self::extremes( eccentricity, diameter )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::Distance;
+    use crate::{
+        graph::GraphTraits,
+        directed_graph::DiGraph,
+        undirected_graph::UnGraph
+    };
+
+    // Path 1 - 2 - 3 - 4 - 5 with a heavy last edge.
+    fn path() -> UnGraph<usize, (), u32> {
+        let mut graph = UnGraph::<usize, (), u32>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 4, 1 ), ( 4, 5, 5 ) ] {
+            graph.add_edge( u, v, weight ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_eccentricity() {
+        let graph = path();
+        let eccentricity = graph.eccentricity();
+        assert_eq!( eccentricity[ &1 ], Distance::Finite( 4 ) );
+        assert_eq!( eccentricity[ &3 ], Distance::Finite( 2 ) );
+        assert_eq!( graph.diameter(), Some( Distance::Finite( 4 ) ) );
+        assert_eq!( graph.radius(), Some( Distance::Finite( 2 ) ) );
+        assert_eq!( graph.center(), BTreeSet::from( [ 3 ] ) );
+        assert_eq!( graph.periphery(), BTreeSet::from( [ 1, 5 ] ) );
+
+        assert_eq!( graph.eccentricity_by( |weight| *weight )[ &1 ], Distance::Finite( 8 ) );
+        assert_eq!( graph.diameter_by( |weight| *weight ), Some( Distance::Finite( 8 ) ) );
+        assert_eq!( graph.radius_by( |weight| *weight ), Some( Distance::Finite( 5 ) ) );
+        assert_eq!( graph.center_by( |weight| *weight ), BTreeSet::from( [ 4 ] ) );
+        assert_eq!( graph.periphery_by( |weight| *weight ), BTreeSet::from( [ 1, 5 ] ) );
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let mut graph = path();
+        graph.add_node( 6, () ).unwrap();
+        assert_eq!( graph.diameter(), Some( Distance::Infinite ) );
+        assert_eq!( graph.radius(), Some( Distance::Infinite ) );
+        assert_eq!( graph.center().len(), 6 );
+        assert_eq!( UnGraph::<usize, (), u32>::new().diameter(), None );
+
+        // Directed paths only reach forwards.
+        let mut directed = DiGraph::<usize, (), ()>::new();
+        for id in 1..=3 {
+            directed.add_node( id, () ).unwrap();
+        }
+        directed.add_edge( 1, 2, () ).unwrap();
+        directed.add_edge( 2, 3, () ).unwrap();
+        directed.add_edge( 3, 1, () ).unwrap();
+        assert_eq!( directed.diameter(), Some( Distance::Finite( 2 ) ) );
+        directed.delete_edge( 3, 1 ).unwrap();
+        let eccentricity = directed.eccentricity();
+        assert_eq!( eccentricity[ &1 ], Distance::Finite( 2 ) );
+        assert!( eccentricity[ &2 ].is_infinite() );
+        assert_eq!( directed.radius().and_then( |radius| radius.finite().copied() ), Some( 2 ) );
+    }
+
+    #[test]
+    fn test_negative_weights() {
+        let mut graph = UnGraph::<usize, (), i32>::new();
+        for id in 1..=3 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 2 ).unwrap();
+        graph.add_edge( 2, 3, -1 ).unwrap();
+        assert_eq!( graph.eccentricity_by( |weight| *weight )[ &1 ], Distance::Infinite );
+        graph.add_edge( 1, 3, 3 ).unwrap();
+        assert_eq!( graph.eccentricity_by( |weight| *weight )[ &2 ], Distance::Finite( 5 ) );
+        assert_eq!( graph.radius_by( |weight| *weight ), Some( Distance::Finite( 3 ) ) );
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Reverse,
+    collections::{ BTreeMap, BinaryHeap, VecDeque }
+};
+
+use crate::graph::{
+    GraphData,
+    NegativeCycle,
+    indexed::Indexed,
+    weight::Weight
+};
+
+// Distances and shortest-path tree over dense indices, `usize::MAX` marks a missing predecessor.
+pub(crate) struct Relaxed<W> {
+    pub distances: Vec<Option<W>>,
+    pub predecessors: Vec<usize>
+}
+
+impl<W> Relaxed<W> {
+    fn new( n: usize ) -> Self {
+        Self {
+            distances: ( 0..n ).map( |_| None ).collect(),
+            predecessors: vec![ usize::MAX; n ]
+        }
+    }
+
+    fn relax( &mut self, u: usize, v: usize, weight: &W ) -> bool
+    where
+        W: Weight
+    {
+        let Some( distance ) = self.distances[ u ].clone() else {
+            return false;
+        };
+        let next = distance + weight.clone();
+        if self.distances[ v ].as_ref().is_none_or( |best| next < *best ) {
+            self.distances[ v ] = Some( next );
+            self.predecessors[ v ] = u;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn into_paths<I>( self, ids: &[I], source: usize ) -> ShortestPaths<I, W>
+    where
+        I: Clone + Ord
+    {
+        let distances = self.distances.into_iter()
+            .enumerate()
+            .filter_map( |( i, distance )| distance.map( |distance| ( ids[ i ].clone(), distance ) ) )
+            .collect();
+        let predecessors = self.predecessors.into_iter()
+            .enumerate()
+            .filter( |&( i, predecessor )| i != source && predecessor != usize::MAX )
+            .map( |( i, predecessor )| ( ids[ i ].clone(), ids[ predecessor ].clone() ) )
+            .collect();
+        ShortestPaths::new( ids[ source ].clone(), distances, predecessors )
+    }
+}
+
+// Any cycle among the predecessor pointers of a relaxation is a negative cycle.
+fn predecessor_cycle( predecessors: &[usize] ) -> Option<Vec<usize>> {
+    let n = predecessors.len();
+    let mut state = vec![ usize::MAX; n ];
+    for root in 0..n {
+        let mut current = root;
+        while current != usize::MAX && state[ current ] == usize::MAX {
+            state[ current ] = root;
+            current = predecessors[ current ];
+        }
+        if current != usize::MAX && state[ current ] == root {
+            let start = current;
+            let mut cycle = vec![ start ];
+            current = predecessors[ start ];
+            while current != start {
+                cycle.push( current );
+                current = predecessors[ current ];
+            }
+            cycle.reverse();
+            return Some( cycle );
+        }
+    }
+    None
+}
+
+pub(crate) fn bellman_ford<W>( adjacency: &[Vec<( usize, W )>], sources: &[usize] ) -> Result<Relaxed<W>, Vec<usize>>
+where
+    W: Weight
+{
+    let n = adjacency.len();
+    let mut relaxed = Relaxed::new( n );
+    for &source in sources {
+        relaxed.distances[ source ] = Some( W::zero() );
+    }
+    if n == 0 {
+        return Ok( relaxed );
+    }
+    for _ in 0..n {
+        let mut changed = false;
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            for ( v, weight ) in edges.iter() {
+                changed |= relaxed.relax( u, *v, weight );
+            }
+        }
+        if !changed {
+            return Ok( relaxed );
+        }
+    }
+    // A relaxation in the n-th pass means n predecessor steps back from the relaxed node end on a cycle.
+    Err( predecessor_cycle( &relaxed.predecessors ).expect( "relaxing after n passes leaves a cycle among the predecessors" ) )
+}
+
+// Queue based Bellman–Ford, a shortest path that grows to n edges proves a negative cycle.
+pub(crate) fn spfa<W>( adjacency: &[Vec<( usize, W )>], sources: &[usize] ) -> Result<Relaxed<W>, Vec<usize>>
+where
+    W: Weight
+{
+    let n = adjacency.len();
+    let mut relaxed = Relaxed::new( n );
+    let mut length = vec![ 0; n ];
+    let mut queued = vec![ false; n ];
+    let mut queue = VecDeque::new();
+    for &source in sources {
+        relaxed.distances[ source ] = Some( W::zero() );
+        queued[ source ] = true;
+        queue.push_back( source );
+    }
+    while let Some( u ) = queue.pop_front() {
+        queued[ u ] = false;
+        for ( v, weight ) in adjacency[ u ].iter() {
+            if relaxed.relax( u, *v, weight ) {
+                length[ *v ] = length[ u ] + 1;
+                if length[ *v ] >= n {
+                    return match predecessor_cycle( &relaxed.predecessors ) {
+                        Some( cycle ) => Err( cycle ),
+                        None => bellman_ford( adjacency, sources )
+                    };
+                }
+                if !queued[ *v ] {
+                    queued[ *v ] = true;
+                    queue.push_back( *v );
+                }
+            }
+        }
+    }
+    Ok( relaxed )
+}
+
+// Nodes taken off the open list and expanded, and entries pushed onto it.
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub struct SearchStats {
+    pub expanded: usize,
+    pub generated: usize
+}
+
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct ShortestPaths<I, W> {
+    source: I,
+    distances: BTreeMap<I, W>,
+    predecessors: BTreeMap<I, I>
+}
+
+impl<I, W> ShortestPaths<I, W>
+where
+    I: Clone + Ord
+{
+    pub(crate) fn new( source: I, distances: BTreeMap<I, W>, predecessors: BTreeMap<I, I> ) -> Self {
+        Self { source, distances, predecessors }
+    }
+
+    pub fn source( &self ) -> &I {
+        &self.source
+    }
+
+    pub fn distances( &self ) -> &BTreeMap<I, W> {
+        &self.distances
+    }
+
+    // The shortest-path tree, every reached node except the source maps to its parent.
+    pub fn predecessors( &self ) -> &BTreeMap<I, I> {
+        &self.predecessors
+    }
+
+    pub fn distance( &self, id: &I ) -> Option<&W> {
+        self.distances.get( id )
+    }
+
+    pub fn path_to( &self, id: &I ) -> Option<Vec<I>> {
+        if !self.distances.contains_key( id ) {
+            return None;
+        }
+        let mut path = VecDeque::new();
+        let mut current = id.clone();
+        while let Some( predecessor ) = self.predecessors.get( &current ) {
+            path.push_front( current );
+            current = predecessor.clone();
+        }
+        path.push_front( current );
+        Some( path.into() )
+    }
+}
+
+impl<I, N, E> GraphData<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Best-first search ordered by distance plus `heuristic`, a zero heuristic makes it Dijkstra.
+    // `cost` returning `None` excludes the edge, `goal` stops the search once it accepts a settled node.
+    // Settled distances are only final without negative costs, so negative edges are skipped.
+    pub(crate) fn search<W, C, G, H>( &self, start: I, mut cost: C, mut goal: G, mut heuristic: H ) -> ( ShortestPaths<I, W>, Option<I>, SearchStats )
+    where
+        W: Weight,
+        C: FnMut( &I, &I, &E ) -> Option<W>,
+        G: FnMut( &I ) -> bool,
+        H: FnMut( &I ) -> W
+    {
+        let mut distances = BTreeMap::new();
+        let mut predecessors = BTreeMap::new();
+        let mut heap = BinaryHeap::new();
+        let mut reached = None;
+        let mut stats = SearchStats::default();
+
+        if self.contains_key( &start ) {
+            distances.insert( start.clone(), W::zero() );
+            heap.push( Reverse( ( heuristic( &start ), W::zero(), start.clone() ) ) );
+            stats.generated += 1;
+        }
+
+        while let Some( Reverse( ( _, distance, position ) ) ) = heap.pop() {
+            if distances.get( &position ).is_some_and( |best| distance > *best ) {
+                continue;
+            }
+            if goal( &position ) {
+                reached = Some( position );
+                break;
+            }
+            stats.expanded += 1;
+            if let Some( current_node ) = self.get( &position ) {
+                for ( next_id, edge ) in current_node.adjacencies().iter() {
+                    if !self.contains_key( next_id ) {
+                        continue;
+                    }
+                    let Some( weight ) = cost( &position, next_id, edge ).filter( |weight| *weight >= W::zero() ) else {
+                        continue;
+                    };
+                    let next_distance = distance.clone() + weight;
+                    if distances.get( next_id ).is_none_or( |best| next_distance < *best ) {
+                        distances.insert( next_id.clone(), next_distance.clone() );
+                        predecessors.insert( next_id.clone(), position.clone() );
+                        heap.push( Reverse( ( next_distance.clone() + heuristic( next_id ), next_distance, next_id.clone() ) ) );
+                        stats.generated += 1;
+                    }
+                }
+            }
+        }
+
+        ( ShortestPaths::new( start, distances, predecessors ), reached, stats )
+    }
+
+    pub(crate) fn dijkstra<W, C, G>( &self, start: I, cost: C, goal: G ) -> ( ShortestPaths<I, W>, Option<I> )
+    where
+        W: Weight,
+        C: FnMut( &I, &I, &E ) -> Option<W>,
+        G: FnMut( &I ) -> bool
+    {
+        let ( paths, reached, _ ) = self.search( start, cost, goal, |_| W::zero() );
+        ( paths, reached )
+    }
+}
+
+impl<I, N, E> GraphData<I, N, E>
+where
+    I: Clone + Ord
+{
+    pub(crate) fn bellman_ford<W, F, R>( &self, start: I, weight: F, relax: R ) -> Result<ShortestPaths<I, W>, NegativeCycle<I>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W,
+        R: Fn( &[Vec<( usize, W )>], &[usize] ) -> Result<Relaxed<W>, Vec<usize>>
+    {
+        let indexed = Indexed::new( self );
+        let Some( source ) = indexed.position( &start ) else {
+            return Ok( ShortestPaths::new( start, BTreeMap::new(), BTreeMap::new() ) );
+        };
+        relax( &indexed.weighted( weight ), &[ source ] )
+            .map( |relaxed| relaxed.into_paths( &indexed.ids, source ) )
+            .map_err( |cycle| NegativeCycle( cycle.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() ) )
+    }
+}
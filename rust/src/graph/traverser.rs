@@ -2,15 +2,36 @@
 
 use std::{
     cmp::{ Ord, PartialEq },
-    collections::{ BTreeSet, BinaryHeap, HashMap, VecDeque },
-    marker::PhantomData
+    collections::{ BTreeSet, VecDeque },
+    marker::PhantomData,
+    ops::Sub
 };
 
 use crate::graph::{
+    GraphData,
     GraphType,
-    GraphTraits
+    GraphTraits,
+    NegativeCycle,
+    all_pairs::DistanceMatrix,
+    k_shortest::KShortestPaths,
+    shortest_path::{ self, SearchStats, ShortestPaths },
+    visit::{ Bfs, Dfs, DfsOrder },
+    weight::Weight
 };
 
+// Path with the fewest edges, shared by the sync and async `dijkstra`. A start that is not a node still
+// reaches itself, as it always has.
+fn fewest_edges<I, N, E>( data: &GraphData<I, N, E>, start: I, end: I ) -> Option<Vec<I>>
+where
+    I: Clone + Ord
+{
+    if start == end {
+        return Some( vec![ start ] );
+    }
+    let ( paths, reached ) = data.dijkstra( start, |_, _, _| Some( 1usize ), |id| *id == end );
+    paths.path_to( &reached? )
+}
+
 pub(crate) trait TraverserAccess<'a, T, I, N, E, G>
 where
     T: GraphType,
@@ -82,48 +103,144 @@ where
         }
     }
 
-    fn dijkstra( &'a self, start: I, end: I ) -> Option<Vec<I>>
+    fn bfs_iter( &'a self, start: I ) -> Bfs<'a, I, N, E> {
+        self.bfs_iter_multi( [ start ] )
+    }
+
+    // Every source starts at depth zero.
+    fn bfs_iter_multi<S>( &'a self, starts: S ) -> Bfs<'a, I, N, E>
     where
-        I: std::hash::Hash
+        S: IntoIterator<Item = I>
     {
-        let mut dist: HashMap<I, usize> = HashMap::new();
-        let mut heap = BinaryHeap::new();
-        let mut predecessors: HashMap<I, I> = HashMap::new();
-
-        // Initialize distances
-        dist.insert( start.clone(), 0 );
-        heap.push( ( 0, start.clone() ) );
-
-        while let Some( ( cost, position ) ) = heap.pop() {
-            if position == end {
-                // Reconstruct the path from end to start
-                let mut path = VecDeque::new();
-                let mut current = end.clone();
-                while let Some( predecessor ) = predecessors.get( &current ) {
-                    path.push_front( current.clone() );
-                    current = predecessor.clone();
-                }
-                path.push_front( start );
-                return Some( path.into_iter().collect() );
-            }
+        Bfs::new( self.graph().data(), starts )
+    }
 
-            if cost > *dist.get( &position ).unwrap_or( &usize::MAX ) {
-                continue;
-            }
+    fn dfs_iter( &'a self, start: I ) -> Dfs<'a, I, N, E> {
+        self.dfs_iter_multi( [ start ] )
+    }
 
-            if let Some( current_node) = self.graph().data().get( &position ) {
-                for next_id in current_node.adjacencies().keys() {
-                    let next_cost = cost + 1;
-                    if next_cost < *dist.get( next_id ).unwrap_or( &usize::MAX ) {
-                        dist.insert( next_id.clone(), next_cost );
-                        predecessors.insert( next_id.clone(), position.clone() );
-                        heap.push( ( next_cost, next_id.clone() ) );
-                    }
-                }
-            }
-        }
+    // Preorder, sources are tried in the given order.
+    fn dfs_iter_multi<S>( &'a self, starts: S ) -> Dfs<'a, I, N, E>
+    where
+        S: IntoIterator<Item = I>
+    {
+        Dfs::new( self.graph().data(), starts, DfsOrder::Preorder )
+    }
 
-        None // Return None if no path is found
+    fn dfs_postorder_iter( &'a self, start: I ) -> Dfs<'a, I, N, E> {
+        self.dfs_postorder_iter_multi( [ start ] )
+    }
+
+    fn dfs_postorder_iter_multi<S>( &'a self, starts: S ) -> Dfs<'a, I, N, E>
+    where
+        S: IntoIterator<Item = I>
+    {
+        Dfs::new( self.graph().data(), starts, DfsOrder::Postorder )
+    }
+
+    fn dijkstra( &'a self, start: I, end: I ) -> Option<Vec<I>> {
+        fewest_edges( self.graph().data(), start, end )
+    }
+
+    // Weights must be non-negative, edges with negative weights are skipped. See `bellman_ford_by`.
+    fn dijkstra_by<W, F>( &'a self, start: I, end: I, weight: F ) -> Option<( Vec<I>, W )>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let ( paths, reached ) = self.graph().data().dijkstra( start, |_, _, edge| Some( weight( edge ) ), |id| *id == end );
+        let end = reached?;
+        Some( ( paths.path_to( &end )?, paths.distance( &end )?.clone() ) )
+    }
+
+    // Weights must be non-negative, edges with negative weights are skipped.
+    fn shortest_paths_by<W, F>( &'a self, start: I, weight: F ) -> ShortestPaths<I, W>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.graph().data().dijkstra( start, |_, _, edge| Some( weight( edge ) ), |_| false ).0
+    }
+
+    // Edge costs must be non-negative, edges with negative costs are skipped.
+    fn astar<W, P, C, H>( &'a self, start: I, goal: P, edge_cost: C, heuristic: H ) -> Option<( Vec<I>, W )>
+    where
+        W: Weight,
+        P: Fn( &I ) -> bool,
+        C: Fn( &E ) -> W,
+        H: Fn( &I ) -> W
+    {
+        self.astar_with_stats( start, goal, edge_cost, heuristic ).0
+    }
+
+    // As `astar`, edges with negative costs are skipped.
+    fn astar_with_stats<W, P, C, H>( &'a self, start: I, goal: P, edge_cost: C, heuristic: H ) -> ( Option<( Vec<I>, W )>, SearchStats )
+    where
+        W: Weight,
+        P: Fn( &I ) -> bool,
+        C: Fn( &E ) -> W,
+        H: Fn( &I ) -> W
+    {
+        let ( paths, reached, stats ) = self.graph().data().search( start, |_, _, edge| Some( edge_cost( edge ) ), goal, heuristic );
+        let path = reached.and_then( |end| Some( ( paths.path_to( &end )?, paths.distance( &end )?.clone() ) ) );
+        ( path, stats )
+    }
+
+    // Heads for the closest of several goals, `heuristic( node, goal )` estimates the cost to a single goal.
+    // Edge costs must be non-negative, edges with negative costs are skipped.
+    fn astar_multi<W, C, H>( &'a self, start: I, goals: BTreeSet<I>, edge_cost: C, heuristic: H ) -> Option<( Vec<I>, W )>
+    where
+        W: Weight,
+        C: Fn( &E ) -> W,
+        H: Fn( &I, &I ) -> W
+    {
+        self.astar(
+            start,
+            |id| goals.contains( id ),
+            edge_cost,
+            |id| goals.iter().map( |goal| heuristic( id, goal ) ).min().unwrap_or( W::zero() )
+        )
+    }
+
+    fn bellman_ford_by<W, F>( &'a self, start: I, weight: F ) -> Result<ShortestPaths<I, W>, NegativeCycle<I>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.graph().data().bellman_ford( start, weight, shortest_path::bellman_ford )
+    }
+
+    fn spfa_by<W, F>( &'a self, start: I, weight: F ) -> Result<ShortestPaths<I, W>, NegativeCycle<I>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.graph().data().bellman_ford( start, weight, shortest_path::spfa )
+    }
+
+    fn floyd_warshall_by<W, F>( &'a self, weight: F ) -> Result<DistanceMatrix<I, W>, NegativeCycle<I>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        self.graph().data().floyd_warshall( weight )
+    }
+
+    fn johnson_by<W, F>( &'a self, weight: F ) -> Result<DistanceMatrix<I, W>, NegativeCycle<I>>
+    where
+        W: Weight + Sub<Output = W>,
+        F: Fn( &E ) -> W
+    {
+        self.graph().data().johnson( weight )
+    }
+
+    // Weights must be non-negative, edges with negative weights are skipped.
+    fn k_shortest_paths_by<W, F>( &'a self, start: I, end: I, weight: F ) -> KShortestPaths<'a, I, N, E, W, F>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        KShortestPaths::new( self.graph().data(), start, end, weight )
     }
 }
 
@@ -186,48 +303,8 @@ where
         }
     }
 
-    async fn dijkstra( &'a self, start: I, end: I ) -> Option<Vec<I>>
-    where
-        I: std::hash::Hash
-    {
-        let mut dist: HashMap<I, usize> = HashMap::new();
-        let mut heap = BinaryHeap::new();
-        let mut predecessors: HashMap<I, I> = HashMap::new();
-
-        // Initialize distances
-        dist.insert( start.clone(), 0 );
-        heap.push( ( 0, start.clone() ) );
-
-        while let Some( ( cost, position ) ) = heap.pop() {
-            if position == end {
-                // Reconstruct the path from end to start
-                let mut path = VecDeque::new();
-                let mut current = end.clone();
-                while let Some( predecessor ) = predecessors.get( &current ) {
-                    path.push_front( current.clone() );
-                    current = predecessor.clone();
-                }
-                path.push_front( start );
-                return Some( path.into_iter().collect() );
-            }
-
-            if cost > *dist.get( &position ).unwrap_or( &usize::MAX ) {
-                continue;
-            }
-
-            if let Some( current_node) = self.graph().data().get( &position ) {
-                for next_id in current_node.adjacencies().keys() {
-                    let next_cost = cost + 1;
-                    if next_cost < *dist.get( next_id ).unwrap_or( &usize::MAX ) {
-                        dist.insert( next_id.clone(), next_cost );
-                        predecessors.insert( next_id.clone(), position.clone() );
-                        heap.push( ( next_cost, next_id.clone() ) );
-                    }
-                }
-            }
-        }
-
-        None // Return None if no path is found
+    async fn dijkstra( &'a self, start: I, end: I ) -> Option<Vec<I>> {
+        fewest_edges( self.graph().data(), start, end )
     }
 }
 
@@ -351,14 +428,33 @@ mod tests {
         graph::{
             Graph,
             GraphTraits,
+            NegativeCycle,
             traverser::{
                 TraverserTraits,
                 Traversable
-            }
+            },
+            weight::Total
         },
-        directed_graph::Directed
+        directed_graph::{
+            Directed,
+            DiGraph
+        },
+        undirected_graph::UnGraph
     };
 
+    fn weighted() -> DiGraph<usize, (), u32> {
+        let mut graph = DiGraph::<usize, (), u32>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 7 ).unwrap();
+        graph.add_edge( 1, 3, 2 ).unwrap();
+        graph.add_edge( 3, 2, 3 ).unwrap();
+        graph.add_edge( 2, 4, 1 ).unwrap();
+        graph.add_edge( 3, 4, 8 ).unwrap();
+        graph
+    }
+
     #[test]
     fn test_dfs() {
         let mut graph = Graph::<Directed, usize, (), ()>::new();
@@ -383,6 +479,54 @@ mod tests {
         graph.traverser().bfs( 1 );
     }
 
+    //     1
+    //    / \
+    //   2   3
+    //  / \   \
+    // 4   5   6 -> 1
+    //
+    // and a separate 7 -> 8.
+    fn branching() -> DiGraph<usize, (), ()> {
+        let mut graph = DiGraph::<usize, (), ()>::new();
+        for id in 1..=8 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 1, 3 ), ( 2, 4 ), ( 2, 5 ), ( 3, 6 ), ( 6, 1 ), ( 7, 8 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let graph = branching();
+        let visited: Vec<( usize, usize )> = graph.traverser().bfs_iter( 1 ).collect();
+        assert_eq!( visited, vec![ ( 1, 0 ), ( 2, 1 ), ( 3, 1 ), ( 4, 2 ), ( 5, 2 ), ( 6, 2 ) ] );
+        assert_eq!( graph.traverser().bfs_iter( 9 ).count(), 0 );
+
+        let visited: Vec<( usize, usize )> = graph.traverser().bfs_iter_multi( [ 7, 3, 9 ] ).collect();
+        assert_eq!( visited, vec![ ( 7, 0 ), ( 3, 0 ), ( 8, 1 ), ( 6, 1 ), ( 1, 2 ), ( 2, 3 ), ( 4, 4 ), ( 5, 4 ) ] );
+    }
+
+    #[test]
+    fn test_dfs_iter() {
+        let graph = branching();
+        assert_eq!( graph.traverser().dfs_iter( 1 ).collect::<Vec<usize>>(), vec![ 1, 2, 4, 5, 3, 6 ] );
+        assert_eq!( graph.traverser().dfs_iter( 6 ).collect::<Vec<usize>>(), vec![ 6, 1, 2, 4, 5, 3 ] );
+        assert_eq!( graph.traverser().dfs_postorder_iter( 1 ).collect::<Vec<usize>>(), vec![ 4, 5, 2, 6, 3, 1 ] );
+        assert_eq!( graph.traverser().dfs_iter_multi( [ 3, 7, 1 ] ).collect::<Vec<usize>>(), vec![ 3, 6, 1, 2, 4, 5, 7, 8 ] );
+        assert_eq!( graph.traverser().dfs_postorder_iter_multi( [ 7, 2 ] ).collect::<Vec<usize>>(), vec![ 8, 7, 4, 5, 2 ] );
+
+        let mut undirected = UnGraph::<usize, (), ()>::new();
+        for id in 1..=4 {
+            undirected.add_node( id, () ).unwrap();
+        }
+        undirected.add_edge( 1, 3, () ).unwrap();
+        undirected.add_edge( 3, 2, () ).unwrap();
+        assert_eq!( undirected.traverser().dfs_iter( 2 ).collect::<Vec<usize>>(), vec![ 2, 3, 1 ] );
+        assert_eq!( undirected.traverser().bfs_iter_multi( [ 4, 1 ] ).map( |( id, _ )| id ).collect::<Vec<usize>>(), vec![ 4, 1, 3, 2 ] );
+    }
+
     #[test]
     fn test_dijkstra() {
         let mut graph = Graph::<Directed, &'static str, &'static str, &'static str>::new();
@@ -421,4 +565,277 @@ mod tests {
         let path = path.unwrap();
         println!( "path: {:?}", path );
     }
+
+    #[test]
+    fn test_dijkstra_by() {
+        let graph = weighted();
+        assert_eq!( graph.traverser().dijkstra_by( 1, 4, |weight| *weight ), Some( ( vec![ 1, 3, 2, 4 ], 6 ) ) );
+        assert_eq!( graph.traverser().dijkstra_by( 1, 1, |weight| *weight ), Some( ( vec![ 1 ], 0 ) ) );
+        assert_eq!( graph.traverser().dijkstra_by( 1, 5, |weight| *weight ), None );
+        assert_eq!( graph.traverser().dijkstra( 1, 4 ), Some( vec![ 1, 2, 4 ] ) );
+        // As before the weighted core, a start that is not a node only reaches itself.
+        assert_eq!( graph.traverser().dijkstra( 9, 9 ), Some( vec![ 9 ] ) );
+        assert_eq!( graph.traverser().dijkstra( 9, 1 ), None );
+    }
+
+    #[test]
+    fn test_dijkstra_by_negative_edges() {
+        let mut graph = DiGraph::<usize, (), i32>::new();
+        for id in 0..4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 0, 1, 1 ), ( 0, 2, 5 ), ( 2, 1, -10 ), ( 1, 3, 1 ), ( 1, 2, -3 ), ( 2, 3, 1 ) ] {
+            graph.add_edge( u, v, weight ).unwrap();
+        }
+        // The negative edges are skipped, so the negative cycle 1 -> 2 -> 1 cannot stall the search.
+        assert_eq!( graph.traverser().dijkstra_by( 0, 3, |weight| *weight ), Some( ( vec![ 0, 1, 3 ], 2 ) ) );
+        assert_eq!( graph.traverser().shortest_paths_by( 0, |weight| *weight ).distance( &1 ), Some( &1 ) );
+        assert_eq!( graph.traverser().astar( 0, |id| *id == 3, |weight| *weight, |_| 0 ), Some( ( vec![ 0, 1, 3 ], 2 ) ) );
+        assert_eq!( graph.traverser().dijkstra_by( 0, 9, |weight| *weight ), None );
+    }
+
+    #[test]
+    fn test_dijkstra_by_float() {
+        let mut graph = UnGraph::<&'static str, (), f64>::new();
+        graph.add_node( "a", () ).unwrap();
+        graph.add_node( "b", () ).unwrap();
+        graph.add_node( "c", () ).unwrap();
+        graph.add_edge( "a", "b", 0.5 ).unwrap();
+        graph.add_edge( "b", "c", 0.25 ).unwrap();
+        graph.add_edge( "a", "c", 1.0 ).unwrap();
+        let ( path, cost ) = graph.traverser().dijkstra_by( "c", "a", |weight| Total( *weight ) ).unwrap();
+        assert_eq!( path, vec![ "c", "b", "a" ] );
+        assert_eq!( cost, Total( 0.75 ) );
+    }
+
+    #[test]
+    fn test_shortest_paths_by() {
+        let graph = weighted();
+        let paths = graph.traverser().shortest_paths_by( 1, |weight| *weight as u64 );
+        assert_eq!( paths.source(), &1 );
+        assert_eq!( paths.distances().iter().map( |( id, distance )| ( *id, *distance ) ).collect::<Vec<_>>(),
+            vec![ ( 1, 0 ), ( 2, 5 ), ( 3, 2 ), ( 4, 6 ) ] );
+        assert_eq!( paths.predecessors().get( &2 ), Some( &3 ) );
+        assert_eq!( paths.path_to( &4 ), Some( vec![ 1, 3, 2, 4 ] ) );
+        assert_eq!( paths.path_to( &5 ), None );
+    }
+
+    #[test]
+    fn test_bellman_ford_by() {
+        let mut graph = DiGraph::<usize, (), i32>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 4 ).unwrap();
+        graph.add_edge( 1, 3, 5 ).unwrap();
+        graph.add_edge( 2, 4, 3 ).unwrap();
+        graph.add_edge( 3, 2, -3 ).unwrap();
+        graph.add_edge( 4, 5, -2 ).unwrap();
+
+        for paths in [ graph.traverser().bellman_ford_by( 1, |weight| *weight ), graph.traverser().spfa_by( 1, |weight| *weight ) ] {
+            let paths = paths.unwrap();
+            assert_eq!( paths.distance( &2 ), Some( &2 ) );
+            assert_eq!( paths.distance( &5 ), Some( &3 ) );
+            assert_eq!( paths.path_to( &5 ), Some( vec![ 1, 3, 2, 4, 5 ] ) );
+        }
+    }
+
+    #[test]
+    fn test_bellman_ford_by_negative_cycle() {
+        let mut graph = DiGraph::<&'static str, (), Total<f64>>::new();
+        for id in [ "usd", "eur", "gbp", "jpy" ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( "usd", "eur", Total( -0.1 ) ).unwrap();
+        graph.add_edge( "eur", "gbp", Total( 0.05 ) ).unwrap();
+        graph.add_edge( "gbp", "usd", Total( -0.02 ) ).unwrap();
+        graph.add_edge( "jpy", "usd", Total( 1.0 ) ).unwrap();
+
+        for result in [ graph.traverser().bellman_ford_by( "usd", |weight| *weight ), graph.traverser().spfa_by( "jpy", |weight| *weight ) ] {
+            let NegativeCycle( mut cycle ) = result.unwrap_err();
+            cycle.sort();
+            assert_eq!( cycle, vec![ "eur", "gbp", "usd" ] );
+        }
+        assert!( graph.traverser().bellman_ford_by( "gbp", |weight| *weight ).is_err() );
+    }
+
+    #[test]
+    fn test_bellman_ford_by_unreachable_cycle() {
+        let mut graph = DiGraph::<usize, (), i64>::new();
+        for id in 1..=3 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 2, 3, -1 ).unwrap();
+        graph.add_edge( 3, 2, -1 ).unwrap();
+        let paths = graph.traverser().spfa_by( 1, |weight| *weight ).unwrap();
+        assert_eq!( paths.distances().len(), 1 );
+    }
+
+    fn grid( width: i64, height: i64, walls: &[ ( i64, i64 ) ] ) -> UnGraph<( i64, i64 ), (), u32> {
+        let mut graph = UnGraph::<( i64, i64 ), (), u32>::new();
+        for x in 0..width {
+            for y in 0..height {
+                if !walls.contains( &( x, y ) ) {
+                    graph.add_node( ( x, y ), () ).unwrap();
+                }
+            }
+        }
+        for x in 0..width {
+            for y in 0..height {
+                for next in [ ( x + 1, y ), ( x, y + 1 ) ] {
+                    if graph.contains_node( ( x, y ) ) && graph.contains_node( next ) {
+                        graph.add_edge( ( x, y ), next, 1 ).unwrap();
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_astar() {
+        let graph = grid( 20, 20, &[ ( 5, 0 ), ( 5, 1 ), ( 5, 2 ), ( 5, 3 ), ( 5, 4 ) ] );
+        let manhattan = |&( x, y ): &( i64, i64 )| ( ( 19 - x ).abs() + ( 19 - y ).abs() ) as u32;
+
+        let ( path, stats ) = graph.traverser().astar_with_stats( ( 0, 0 ), |id| *id == ( 19, 19 ), |weight| *weight, manhattan );
+        let ( path, cost ) = path.unwrap();
+        assert_eq!( cost, 38 );
+        assert_eq!( path.len(), 39 );
+        assert_eq!( path.first(), Some( &( 0, 0 ) ) );
+        assert_eq!( path.last(), Some( &( 19, 19 ) ) );
+
+        let ( dijkstra, dijkstra_stats ) = graph.traverser().astar_with_stats( ( 0, 0 ), |id| *id == ( 19, 19 ), |weight| *weight, |_| 0 );
+        assert_eq!( dijkstra.map( |( _, cost )| cost ), Some( 38 ) );
+        assert!( stats.expanded < dijkstra_stats.expanded );
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let graph = grid( 3, 3, &[ ( 1, 0 ), ( 1, 1 ), ( 1, 2 ) ] );
+        assert_eq!( graph.traverser().astar( ( 0, 0 ), |id| *id == ( 2, 2 ), |weight| *weight, |_| 0 ), None );
+    }
+
+    #[test]
+    fn test_astar_multi() {
+        let graph = grid( 10, 10, &[] );
+        let goals = [ ( 9, 9 ), ( 2, 3 ), ( 0, 8 ) ].into_iter().collect();
+        let distance = |&( x1, y1 ): &( i64, i64 ), &( x2, y2 ): &( i64, i64 )| ( ( x1 - x2 ).abs() + ( y1 - y2 ).abs() ) as u32;
+        let ( path, cost ) = graph.traverser().astar_multi( ( 0, 0 ), goals, |weight| *weight, distance ).unwrap();
+        assert_eq!( path.last(), Some( &( 2, 3 ) ) );
+        assert_eq!( cost, 5 );
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let mut graph = DiGraph::<char, (), i32>::new();
+        for id in [ 'a', 'b', 'c', 'd', 'e' ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 'a', 'b', 3 ).unwrap();
+        graph.add_edge( 'a', 'c', 8 ).unwrap();
+        graph.add_edge( 'a', 'e', -4 ).unwrap();
+        graph.add_edge( 'b', 'd', 1 ).unwrap();
+        graph.add_edge( 'b', 'e', 7 ).unwrap();
+        graph.add_edge( 'c', 'b', 4 ).unwrap();
+        graph.add_edge( 'd', 'a', 2 ).unwrap();
+        graph.add_edge( 'd', 'c', -5 ).unwrap();
+        graph.add_edge( 'e', 'd', 6 ).unwrap();
+
+        let floyd = graph.traverser().floyd_warshall_by( |weight| *weight ).unwrap();
+        let johnson = graph.traverser().johnson_by( |weight| *weight ).unwrap();
+        for matrix in [ &floyd, &johnson ] {
+            assert_eq!( matrix.distance( &'a', &'b' ), Some( &1 ) );
+            assert_eq!( matrix.distance( &'e', &'b' ), Some( &5 ) );
+            assert_eq!( matrix.distance( &'c', &'e' ), Some( &3 ) );
+            assert_eq!( matrix.distance( &'b', &'b' ), Some( &0 ) );
+            assert_eq!( matrix.path( &'a', &'b' ), Some( vec![ 'a', 'e', 'd', 'c', 'b' ] ) );
+            assert_eq!( matrix.path( &'d', &'d' ), Some( vec![ 'd' ] ) );
+        }
+        for from in floyd.ids() {
+            for to in floyd.ids() {
+                assert_eq!( floyd.distance( from, to ), johnson.distance( from, to ) );
+                assert_eq!( floyd.path( from, to ), johnson.path( from, to ) );
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_unreachable() {
+        let mut graph = DiGraph::<usize, (), u32>::new();
+        graph.add_node( 1, () ).unwrap();
+        graph.add_node( 2, () ).unwrap();
+        graph.add_edge( 1, 2, 4 ).unwrap();
+        let matrix = graph.traverser().johnson_by( |weight| *weight ).unwrap();
+        assert_eq!( matrix.distance( &1, &2 ), Some( &4 ) );
+        assert_eq!( matrix.distance( &2, &1 ), None );
+        assert_eq!( matrix.path( &2, &1 ), None );
+    }
+
+    #[test]
+    fn test_all_pairs_negative_cycle() {
+        let mut graph = DiGraph::<usize, (), i32>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 1 ).unwrap();
+        graph.add_edge( 2, 3, -2 ).unwrap();
+        graph.add_edge( 3, 2, 1 ).unwrap();
+        graph.add_edge( 3, 4, 1 ).unwrap();
+        for result in [ graph.traverser().floyd_warshall_by( |weight| *weight ), graph.traverser().johnson_by( |weight| *weight ) ] {
+            let NegativeCycle( mut cycle ) = result.unwrap_err();
+            cycle.sort();
+            assert_eq!( cycle, vec![ 2, 3 ] );
+        }
+    }
+
+    #[test]
+    fn test_floyd_warshall_dense_negative() {
+        let mut graph = DiGraph::<usize, (), i64>::new();
+        for id in 0..80 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for u in 0..80 {
+            for v in ( 0..80 ).filter( |&v| v != u ) {
+                graph.add_edge( u, v, -1 ).unwrap();
+            }
+        }
+        for result in [ graph.traverser().floyd_warshall_by( |weight| *weight ), graph.traverser().johnson_by( |weight| *weight ) ] {
+            assert!( !result.unwrap_err().0.is_empty() );
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let mut graph = DiGraph::<char, (), u32>::new();
+        for id in [ 'c', 'd', 'e', 'f', 'g', 'h' ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 'c', 'd', 3 ), ( 'c', 'e', 2 ), ( 'd', 'f', 4 ), ( 'e', 'd', 1 ), ( 'e', 'f', 2 ), ( 'e', 'g', 3 ), ( 'f', 'g', 2 ), ( 'f', 'h', 1 ), ( 'g', 'h', 2 ) ] {
+            graph.add_edge( u, v, weight ).unwrap();
+        }
+        let traverser = graph.traverser();
+        let paths: Vec<( String, u32 )> = traverser.k_shortest_paths_by( 'c', 'h', |weight| *weight )
+            .map( |( path, cost )| ( path.into_iter().collect(), cost ) )
+            .collect();
+        assert_eq!( paths, vec![
+            ( "cefh".to_string(), 5 ),
+            ( "cegh".to_string(), 7 ),
+            ( "cdfh".to_string(), 8 ),
+            ( "cedfh".to_string(), 8 ),
+            ( "cefgh".to_string(), 8 ),
+            ( "cdfgh".to_string(), 11 ),
+            ( "cedfgh".to_string(), 11 )
+        ] );
+        assert_eq!( traverser.k_shortest_paths_by( 'h', 'c', |weight| *weight ).next(), None );
+        assert_eq!( traverser.k_shortest_paths_by( 'c', 'c', |weight| *weight ).collect::<Vec<_>>(), vec![ ( vec![ 'c' ], 0 ) ] );
+
+        let mut negative = DiGraph::<char, (), i32>::new();
+        for id in [ 'a', 'b', 'c' ] {
+            negative.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 'a', 'b', 1 ), ( 'b', 'c', 1 ), ( 'a', 'c', -1 ), ( 'c', 'a', -1 ) ] {
+            negative.add_edge( u, v, weight ).unwrap();
+        }
+        assert_eq!( negative.traverser().k_shortest_paths_by( 'a', 'c', |weight| *weight ).collect::<Vec<_>>(), vec![ ( vec![ 'a', 'b', 'c' ], 2 ) ] );
+    }
 }
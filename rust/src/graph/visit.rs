@@ -0,0 +1,125 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::{ BTreeSet, VecDeque, btree_map::Keys };
+
+use crate::graph::GraphData;
+
+// Breadth first order from one or more sources, every node comes with the number of edges from the
+// nearest source. Sources that are not nodes of the graph are skipped.
+pub struct Bfs<'g, I, N, E> {
+    data: &'g GraphData<I, N, E>,
+    queue: VecDeque<( I, usize )>,
+    visited: BTreeSet<I>
+}
+
+impl<'g, I, N, E> Bfs<'g, I, N, E>
+where
+    I: Clone + Ord
+{
+    pub(crate) fn new<S>( data: &'g GraphData<I, N, E>, starts: S ) -> Self
+    where
+        S: IntoIterator<Item = I>
+    {
+        let mut visited = BTreeSet::new();
+        let queue = starts.into_iter()
+            .filter( |start| data.contains_key( start ) && visited.insert( start.clone() ) )
+            .map( |start| ( start, 0 ) )
+            .collect();
+        Self { data, queue, visited }
+    }
+}
+
+impl<I, N, E> Iterator for Bfs<'_, I, N, E>
+where
+    I: Clone + Ord
+{
+    type Item = ( I, usize );
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        let ( id, depth ) = self.queue.pop_front()?;
+        if let Some( node ) = self.data.get( &id ) {
+            for next in node.adjacencies().keys() {
+                if self.data.contains_key( next ) && self.visited.insert( next.clone() ) {
+                    self.queue.push_back( ( next.clone(), depth + 1 ) );
+                }
+            }
+        }
+        Some( ( id, depth ) )
+    }
+}
+
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum DfsOrder {
+    Preorder,
+    Postorder
+}
+
+// Depth first order from one or more sources, neighbours are followed in ascending order. Every source
+// that is still unvisited starts a new tree, sources that are not nodes of the graph are skipped.
+pub struct Dfs<'g, I, N, E> {
+    data: &'g GraphData<I, N, E>,
+    order: DfsOrder,
+    starts: std::vec::IntoIter<I>,
+    stack: Vec<( I, Keys<'g, I, E> )>,
+    visited: BTreeSet<I>
+}
+
+impl<'g, I, N, E> Dfs<'g, I, N, E>
+where
+    I: Clone + Ord
+{
+    pub(crate) fn new<S>( data: &'g GraphData<I, N, E>, starts: S, order: DfsOrder ) -> Self
+    where
+        S: IntoIterator<Item = I>
+    {
+        Self {
+            data,
+            order,
+            starts: starts.into_iter().collect::<Vec<I>>().into_iter(),
+            stack: Vec::new(),
+            visited: BTreeSet::new()
+        }
+    }
+
+    fn enter( &mut self, id: &I ) -> bool {
+        let Some( node ) = self.data.get( id ).filter( |_| !self.visited.contains( id ) ) else {
+            return false;
+        };
+        self.visited.insert( id.clone() );
+        self.stack.push( ( id.clone(), node.adjacencies().keys() ) );
+        true
+    }
+}
+
+impl<I, N, E> Iterator for Dfs<'_, I, N, E>
+where
+    I: Clone + Ord
+{
+    type Item = I;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        loop {
+            let Some( ( _, neighbors ) ) = self.stack.last_mut() else {
+                let start = self.starts.next()?;
+                if self.enter( &start ) && self.order == DfsOrder::Preorder {
+                    return Some( start );
+                }
+                continue;
+            };
+            match neighbors.next() {
+                Some( next ) => {
+                    if self.enter( next ) && self.order == DfsOrder::Preorder {
+                        return Some( next.clone() );
+                    }
+                }
+                None => {
+                    let ( id, _ ) = self.stack.pop()?;
+                    if self.order == DfsOrder::Postorder {
+                        return Some( id );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Ordering,
+    ops::{ Add, Neg, Sub }
+};
+
+pub trait Weight: Clone + Ord + Add<Output = Self> {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_weight {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Weight for $t {
+                #[inline(always)]
+                fn zero() -> Self {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!( u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize );
+
+// Floats are not `Ord`, wrap them to order by `total_cmp`.
+#[derive( Debug, Clone, Copy, Default )]
+pub struct Total<F>( pub F );
+
+macro_rules! impl_total {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl PartialEq for Total<$t> {
+                fn eq( &self, other: &Self ) -> bool {
+                    self.cmp( other ) == Ordering::Equal
+                }
+            }
+
+            impl Eq for Total<$t> {}
+
+            impl PartialOrd for Total<$t> {
+                fn partial_cmp( &self, other: &Self ) -> Option<Ordering> {
+                    Some( self.cmp( other ) )
+                }
+            }
+
+            impl Ord for Total<$t> {
+                fn cmp( &self, other: &Self ) -> Ordering {
+                    self.0.total_cmp( &other.0 )
+                }
+            }
+
+            impl Add for Total<$t> {
+                type Output = Self;
+                fn add( self, other: Self ) -> Self {
+                    Total( self.0 + other.0 )
+                }
+            }
+
+            impl Sub for Total<$t> {
+                type Output = Self;
+                fn sub( self, other: Self ) -> Self {
+                    Total( self.0 - other.0 )
+                }
+            }
+
+            impl Neg for Total<$t> {
+                type Output = Self;
+                fn neg( self ) -> Self {
+                    Total( -self.0 )
+                }
+            }
+
+            impl Weight for Total<$t> {
+                #[inline(always)]
+                fn zero() -> Self {
+                    Total( 0.0 )
+                }
+            }
+        )*
+    };
+}
+
+impl_total!( f32, f64 );
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::weight::{ Total, Weight };
+
+    #[test]
+    fn test_total_order() {
+        let mut weights = [ Total( 2.5 ), Total( -1.0 ), Total( 0.0 ), Total( f64::INFINITY ) ];
+        weights.sort();
+        assert_eq!( weights.iter().map( |w| w.0 ).collect::<Vec<f64>>(), vec![ -1.0, 0.0, 2.5, f64::INFINITY ] );
+        assert_eq!( Total( 1.5 ) + Total( 2.0 ), Total( 3.5 ) );
+        assert_eq!( Total::<f32>::zero(), Total( 0.0 ) );
+    }
+}
@@ -1,5 +1,15 @@
 // Copyright 2024 Bewusstsein Labs
 
+pub mod cliques;
+pub mod coloring;
+pub mod community;
+pub mod components;
+pub mod covering;
+pub mod cycles;
+pub mod euler;
+pub mod planarity;
+pub mod spanning_tree;
+
 //: Standard
 use std::fmt::Display;
 
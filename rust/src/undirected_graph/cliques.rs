@@ -0,0 +1,150 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::{
+        UnGraph,
+        coloring::{ degeneracy_order, simple }
+    }
+};
+
+fn intersect( set: &[usize], neighbors: &[usize] ) -> Vec<usize> {
+    set.iter().copied().filter( |v| neighbors.binary_search( v ).is_ok() ).collect()
+}
+
+// Bron–Kerbosch with Tomita pivoting. The outer level walks a degeneracy ordering so every branch
+// starts from at most degeneracy many candidates. Cliques come out with their nodes sorted.
+#[derive( Debug, Clone )]
+pub struct MaximalCliques<I> {
+    ids: Vec<I>,
+    adjacency: Vec<Vec<usize>>,
+    // ( clique, candidates, excluded )
+    stack: Vec<( Vec<usize>, Vec<usize>, Vec<usize> )>
+}
+
+impl<I> MaximalCliques<I>
+where
+    I: Clone
+{
+    fn new( ids: Vec<I>, adjacency: Vec<Vec<usize>> ) -> Self {
+        let order = degeneracy_order( &adjacency );
+        let mut position = vec![ 0; order.len() ];
+        order.iter().enumerate().for_each( |( i, &v )| position[ v ] = i );
+
+        let stack = order.iter().rev()
+            .map( |&v| {
+                let ( later, earlier ) = adjacency[ v ].iter().partition( |&&w| position[ w ] > position[ v ] );
+                ( vec![ v ], later, earlier )
+            } )
+            .collect();
+        Self { ids, adjacency, stack }
+    }
+}
+
+impl<I> Iterator for MaximalCliques<I>
+where
+    I: Clone
+{
+    type Item = Vec<I>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        while let Some( ( clique, mut candidates, mut excluded ) ) = self.stack.pop() {
+            if candidates.is_empty() {
+                if excluded.is_empty() {
+                    let mut clique = clique;
+                    clique.sort_unstable();
+                    return Some( clique.into_iter().map( |v| self.ids[ v ].clone() ).collect() );
+                }
+                continue;
+            }
+
+            let pivot = candidates.iter().chain( excluded.iter() ).copied()
+                .max_by_key( |&u| intersect( &candidates, &self.adjacency[ u ] ).len() )
+                .unwrap_or( candidates[ 0 ] );
+            let branches: Vec<usize> = candidates.iter().copied()
+                .filter( |v| self.adjacency[ pivot ].binary_search( v ).is_err() )
+                .collect();
+
+            let mut children = Vec::with_capacity( branches.len() );
+            for v in branches {
+                let mut child = clique.clone();
+                child.push( v );
+                children.push( ( child, intersect( &candidates, &self.adjacency[ v ] ), intersect( &excluded, &self.adjacency[ v ] ) ) );
+                candidates.retain( |&w| w != v );
+                excluded.push( v );
+            }
+            self.stack.extend( children.into_iter().rev() );
+        }
+        None
+    }
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Self loops are ignored and isolated nodes are cliques of their own.
+    pub fn maximal_cliques( &self ) -> MaximalCliques<I> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        MaximalCliques::new( indexed.ids, adjacency )
+    }
+
+    // Largest maximal clique, the first one found on ties. Exponential in the worst case.
+    pub fn maximum_clique( &self ) -> Vec<I> {
+        self.maximal_cliques().fold( Vec::new(), |best, clique| if clique.len() > best.len() { clique } else { best } )
+    }
+
+    pub fn clique_number( &self ) -> usize {
+        self.maximum_clique().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{
+        GraphTraits,
+        fixtures::ungraph
+    };
+
+    #[test]
+    fn test_maximal_cliques() {
+        // Two triangles sharing the edge 1 - 2, a square 3 - 4 - 5 - 6 and the isolated node 7.
+        let graph = ungraph( 8, &[ ( 0, 1 ), ( 0, 2 ), ( 1, 2 ), ( 1, 3 ), ( 2, 3 ), ( 3, 4 ), ( 4, 5 ), ( 5, 6 ), ( 6, 3 ) ] );
+        let mut cliques: Vec<Vec<usize>> = graph.maximal_cliques().collect();
+        cliques.sort();
+        assert_eq!( cliques, vec![ vec![ 0, 1, 2 ], vec![ 1, 2, 3 ], vec![ 3, 4 ], vec![ 3, 6 ], vec![ 4, 5 ], vec![ 5, 6 ], vec![ 7 ] ] );
+    }
+
+    #[test]
+    fn test_maximal_cliques_count() {
+        // Moon–Moser graph, the complement of three disjoint triangles, has 3^3 maximal cliques.
+        let mut graph = ungraph( 9, &[] );
+        for u in 0..9 {
+            for v in ( u + 1 )..9 {
+                if u / 3 != v / 3 {
+                    graph.add_edge( u, v, () ).unwrap();
+                }
+            }
+        }
+        assert_eq!( graph.maximal_cliques().count(), 27 );
+        assert!( graph.maximal_cliques().all( |clique| clique.len() == 3 ) );
+    }
+
+    #[test]
+    fn test_maximum_clique() {
+        let mut graph = ungraph( 7, &[ ( 0, 1 ), ( 1, 2 ), ( 2, 0 ), ( 5, 6 ) ] );
+        for u in 2..6 {
+            for v in ( u + 1 )..6 {
+                graph.add_edge( u, v, () ).unwrap();
+            }
+        }
+        assert_eq!( graph.maximum_clique(), vec![ 2, 3, 4, 5 ] );
+        assert_eq!( graph.clique_number(), 4 );
+        assert_eq!( ungraph( 0, &[] ).clique_number(), 0 );
+        assert_eq!( ungraph( 3, &[] ).clique_number(), 1 );
+    }
+}
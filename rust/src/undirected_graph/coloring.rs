@@ -0,0 +1,345 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::Reverse,
+    collections::{ BTreeMap, BTreeSet }
+};
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::UnGraph
+};
+
+#[derive( Debug, Clone, Copy, Default, PartialEq, Eq )]
+pub enum ColoringOrder {
+    // Ascending node ids.
+    #[default]
+    Natural,
+    // Highest degree first, the Welsh–Powell ordering.
+    LargestFirst,
+    // Repeatedly strip a node of minimum degree and color in reverse, uses at most degeneracy + 1 colors.
+    SmallestLast
+}
+
+// Neighbour lists without self loops.
+pub(crate) fn simple<I, E>( indexed: &Indexed<'_, I, E> ) -> Vec<Vec<usize>>
+where
+    I: Clone + Ord
+{
+    let mut adjacency = indexed.neighbors();
+    for ( v, edges ) in adjacency.iter_mut().enumerate() {
+        edges.retain( |&w| w != v );
+    }
+    adjacency
+}
+
+// Repeatedly removes a node of minimum remaining degree, returns the removal order.
+pub(crate) fn degeneracy_order( adjacency: &[Vec<usize>] ) -> Vec<usize> {
+    let n = adjacency.len();
+    let mut degrees: Vec<usize> = adjacency.iter().map( Vec::len ).collect();
+    let mut queue: BTreeSet<( usize, usize )> = ( 0..n ).map( |v| ( degrees[ v ], v ) ).collect();
+    let mut removed = vec![ false; n ];
+    let mut order = Vec::with_capacity( n );
+    while let Some( ( _, v ) ) = queue.pop_first() {
+        removed[ v ] = true;
+        order.push( v );
+        for &w in adjacency[ v ].iter().filter( |&&w| !removed[ w ] ) {
+            queue.remove( &( degrees[ w ], w ) );
+            degrees[ w ] -= 1;
+            queue.insert( ( degrees[ w ], w ) );
+        }
+    }
+    order
+}
+
+fn smallest_free( adjacency: &[Vec<usize>], colors: &[usize], v: usize ) -> usize {
+    let used: BTreeSet<usize> = adjacency[ v ].iter().map( |&w| colors[ w ] ).filter( |&c| c != usize::MAX ).collect();
+    ( 0.. ).find( |c| !used.contains( c ) ).unwrap_or( 0 )
+}
+
+fn greedy( adjacency: &[Vec<usize>], order: &[usize] ) -> Vec<usize> {
+    let mut colors = vec![ usize::MAX; adjacency.len() ];
+    for &v in order.iter() {
+        colors[ v ] = smallest_free( adjacency, &colors, v );
+    }
+    colors
+}
+
+// Uncolored node with the most distinct neighbour colors, ties broken by degree then index.
+fn most_saturated( adjacency: &[Vec<usize>], colors: &[usize] ) -> Option<usize> {
+    ( 0..adjacency.len() )
+        .filter( |&v| colors[ v ] == usize::MAX )
+        .min_by_key( |&v| {
+            let saturation = adjacency[ v ].iter().map( |&w| colors[ w ] ).filter( |&c| c != usize::MAX ).collect::<BTreeSet<_>>().len();
+            ( Reverse( saturation ), Reverse( adjacency[ v ].len() ), v )
+        } )
+}
+
+fn dsatur( adjacency: &[Vec<usize>] ) -> Vec<usize> {
+    let mut colors = vec![ usize::MAX; adjacency.len() ];
+    while let Some( v ) = most_saturated( adjacency, &colors ) {
+        colors[ v ] = smallest_free( adjacency, &colors, v );
+    }
+    colors
+}
+
+fn count( colors: &[usize] ) -> usize {
+    colors.iter().max().map_or( 0, |&c| c + 1 )
+}
+
+// DSatur branch and bound, only colorings with fewer colors than `best` are explored.
+fn exact( adjacency: &[Vec<usize>], colors: &mut Vec<usize>, used: usize, best: &mut Vec<usize> ) {
+    let Some( v ) = most_saturated( adjacency, colors ) else {
+        *best = colors.clone();
+        return;
+    };
+    for color in 0..( used + 1 ).min( count( best ) - 1 ) {
+        if adjacency[ v ].iter().any( |&w| colors[ w ] == color ) {
+            continue;
+        }
+        colors[ v ] = color;
+        exact( adjacency, colors, used.max( color + 1 ), best );
+        colors[ v ] = usize::MAX;
+    }
+}
+
+// Misra–Gries, colors every edge of a simple graph with at most max degree + 1 colors.
+struct EdgeColoring {
+    at: Vec<Vec<Option<usize>>>,
+    colors: BTreeMap<( usize, usize ), usize>
+}
+
+impl EdgeColoring {
+    fn color( &self, a: usize, b: usize ) -> Option<usize> {
+        self.colors.get( &( a.min( b ), a.max( b ) ) ).copied()
+    }
+
+    fn free( &self, v: usize, color: usize ) -> bool {
+        self.at[ v ][ color ].is_none()
+    }
+
+    fn first_free( &self, v: usize ) -> usize {
+        ( 0..self.at[ v ].len() ).find( |&color| self.free( v, color ) ).unwrap_or( 0 )
+    }
+
+    fn set( &mut self, a: usize, b: usize, color: usize ) {
+        self.at[ a ][ color ] = Some( b );
+        self.at[ b ][ color ] = Some( a );
+        self.colors.insert( ( a.min( b ), a.max( b ) ), color );
+    }
+
+    fn unset( &mut self, a: usize, b: usize ) {
+        if let Some( color ) = self.colors.remove( &( a.min( b ), a.max( b ) ) ) {
+            self.at[ a ][ color ] = None;
+            self.at[ b ][ color ] = None;
+        }
+    }
+
+    fn new( adjacency: &[Vec<usize>] ) -> Self {
+        let colors = adjacency.iter().map( Vec::len ).max().unwrap_or( 0 ) + 1;
+        let mut coloring = Self { at: vec![ vec![ None; colors ]; adjacency.len() ], colors: BTreeMap::new() };
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            for &v in edges.iter().filter( |&&v| u < v ) {
+                coloring.add( adjacency, u, v );
+            }
+        }
+        coloring
+    }
+
+    fn add( &mut self, adjacency: &[Vec<usize>], u: usize, v: usize ) {
+        // Maximal fan of u starting at v.
+        let mut fan = vec![ v ];
+        while let Some( w ) = adjacency[ u ].iter().copied().find( |&w| !fan.contains( &w )
+            && self.color( u, w ).is_some_and( |color| self.free( *fan.last().unwrap(), color ) )
+        ) {
+            fan.push( w );
+        }
+        let c = self.first_free( u );
+        let d = self.first_free( *fan.last().unwrap() );
+
+        // Invert the cd path starting at u, afterwards d is free on u.
+        let mut path = Vec::new();
+        let ( mut x, mut color ) = ( u, d );
+        while let Some( y ) = self.at[ x ][ color ] {
+            path.push( ( x, y, color ) );
+            x = y;
+            color = if color == c { d } else { c };
+        }
+        path.iter().for_each( |&( x, y, _ )| self.unset( x, y ) );
+        path.into_iter().for_each( |( x, y, color )| self.set( x, y, if color == c { d } else { c } ) );
+
+        // Shortest prefix of the fan that is still a fan and ends on a node with d free.
+        let mut end = 0;
+        for i in 0..fan.len() {
+            if i > 0 && !self.color( u, fan[ i ] ).is_some_and( |color| self.free( fan[ i - 1 ], color ) ) {
+                break;
+            }
+            end = i;
+            if self.free( fan[ i ], d ) {
+                break;
+            }
+        }
+        for i in 0..end {
+            if let Some( color ) = self.color( u, fan[ i + 1 ] ) {
+                self.unset( u, fan[ i + 1 ] );
+                self.set( u, fan[ i ], color );
+            }
+        }
+        self.set( u, fan[ end ], d );
+    }
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn colored( indexed: &Indexed<'_, I, E>, colors: Vec<usize> ) -> BTreeMap<I, usize> {
+        indexed.ids.iter().cloned().zip( colors ).collect()
+    }
+
+    pub fn greedy_coloring( &self, order: ColoringOrder ) -> BTreeMap<I, usize> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        let mut nodes: Vec<usize> = ( 0..adjacency.len() ).collect();
+        match order {
+            ColoringOrder::Natural => {},
+            ColoringOrder::LargestFirst => nodes.sort_by_key( |&v| Reverse( adjacency[ v ].len() ) ),
+            ColoringOrder::SmallestLast => {
+                nodes = degeneracy_order( &adjacency );
+                nodes.reverse();
+            }
+        }
+        Self::colored( &indexed, greedy( &adjacency, &nodes ) )
+    }
+
+    pub fn dsatur_coloring( &self ) -> BTreeMap<I, usize> {
+        let indexed = Indexed::new( self.data() );
+        Self::colored( &indexed, dsatur( &simple( &indexed ) ) )
+    }
+
+    // Optimal coloring by branch and bound seeded with DSatur, exponential in the worst case.
+    pub fn exact_coloring( &self ) -> BTreeMap<I, usize> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        let mut best = dsatur( &adjacency );
+        let mut colors = vec![ usize::MAX; adjacency.len() ];
+        exact( &adjacency, &mut colors, 0, &mut best );
+        Self::colored( &indexed, best )
+    }
+
+    pub fn chromatic_number( &self ) -> usize {
+        self.exact_coloring().values().max().map_or( 0, |&c| c + 1 )
+    }
+
+    // Every node colored and no edge joining two nodes of the same color, self loops are ignored.
+    pub fn is_valid_coloring( &self, colors: &BTreeMap<I, usize> ) -> bool {
+        self.data().iter().all( |( id, node )| colors.get( id ).is_some_and( |color| node.adjacencies().keys()
+            .filter( |&neighbor| neighbor != id )
+            .all( |neighbor| colors.get( neighbor ) != Some( color ) )
+        ) )
+    }
+
+    // Colors every edge, keyed as ( smaller id, larger id ), with at most max degree + 1 colors.
+    pub fn edge_coloring( &self ) -> BTreeMap<( I, I ), usize> {
+        let indexed = Indexed::new( self.data() );
+        EdgeColoring::new( &simple( &indexed ) ).colors.into_iter()
+            .map( |( ( u, v ), color )| ( ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() ), color ) )
+            .collect()
+    }
+
+    // Every edge colored and no two edges sharing a node with the same color, self loops are ignored.
+    pub fn is_valid_edge_coloring( &self, colors: &BTreeMap<( I, I ), usize> ) -> bool {
+        self.data().iter().all( |( id, node )| {
+            let mut seen = BTreeSet::new();
+            node.adjacencies().keys()
+                .filter( |&neighbor| neighbor != id )
+                .all( |neighbor| {
+                    let key = if id < neighbor { ( id.clone(), neighbor.clone() ) } else { ( neighbor.clone(), id.clone() ) };
+                    colors.get( &key ).is_some_and( |&color| seen.insert( color ) )
+                } )
+        } )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::ColoringOrder;
+    use crate::{
+        graph::{
+            GraphTraits,
+            fixtures::{ petersen, ungraph }
+        },
+        undirected_graph::UnGraph
+    };
+
+    fn colors( coloring: &BTreeMap<usize, usize> ) -> usize {
+        coloring.values().max().map_or( 0, |&c| c + 1 )
+    }
+
+    // Odd wheel: a five cycle around a hub, needs four colors.
+    fn wheel() -> UnGraph<usize, (), ()> {
+        ungraph( 6, &[ ( 1, 2 ), ( 2, 3 ), ( 3, 4 ), ( 4, 5 ), ( 5, 1 ), ( 0, 1 ), ( 0, 2 ), ( 0, 3 ), ( 0, 4 ), ( 0, 5 ) ] )
+    }
+
+    #[test]
+    fn test_greedy_coloring() {
+        let graph = wheel();
+        for order in [ ColoringOrder::Natural, ColoringOrder::LargestFirst, ColoringOrder::SmallestLast ] {
+            let coloring = graph.greedy_coloring( order );
+            assert!( graph.is_valid_coloring( &coloring ) );
+            assert_eq!( colors( &coloring ), 4 );
+        }
+
+        // Crown graph, natural order alternates sides and needs one color per pair.
+        let crown = ungraph( 8, &[ ( 0, 3 ), ( 0, 5 ), ( 0, 7 ), ( 2, 1 ), ( 2, 5 ), ( 2, 7 ), ( 4, 1 ), ( 4, 3 ), ( 4, 7 ), ( 6, 1 ), ( 6, 3 ), ( 6, 5 ) ] );
+        assert_eq!( colors( &crown.greedy_coloring( ColoringOrder::Natural ) ), 4 );
+        assert_eq!( colors( &crown.greedy_coloring( ColoringOrder::SmallestLast ) ), 2 );
+        assert_eq!( colors( &crown.dsatur_coloring() ), 2 );
+    }
+
+    #[test]
+    fn test_exact_coloring() {
+        let graph = wheel();
+        assert_eq!( graph.chromatic_number(), 4 );
+        let petersen = petersen();
+        let coloring = petersen.exact_coloring();
+        assert!( petersen.is_valid_coloring( &coloring ) );
+        assert_eq!( colors( &coloring ), 3 );
+        assert_eq!( ungraph( 3, &[] ).chromatic_number(), 1 );
+        assert_eq!( ungraph( 0, &[] ).chromatic_number(), 0 );
+
+        let mut invalid = coloring.clone();
+        invalid.insert( 0, coloring[ &1 ] );
+        assert!( !petersen.is_valid_coloring( &invalid ) );
+    }
+
+    #[test]
+    fn test_edge_coloring() {
+        let graph = wheel();
+        let coloring = graph.edge_coloring();
+        assert_eq!( coloring.len(), 10 );
+        assert!( graph.is_valid_edge_coloring( &coloring ) );
+        assert!( coloring.values().all( |&color| color <= 5 ) );
+
+        let mut complete = ungraph( 7, &[] );
+        for u in 0..7 {
+            for v in ( u + 1 )..7 {
+                complete.add_edge( u, v, () ).unwrap();
+            }
+        }
+        let coloring = complete.edge_coloring();
+        assert_eq!( coloring.len(), 21 );
+        assert!( complete.is_valid_edge_coloring( &coloring ) );
+        assert!( coloring.values().all( |&color| color <= 6 ) );
+
+        let mut missing = coloring.clone();
+        missing.remove( &( 0, 1 ) );
+        assert!( !complete.is_valid_edge_coloring( &missing ) );
+    }
+}
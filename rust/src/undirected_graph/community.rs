@@ -0,0 +1,323 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::{ BTreeMap, BTreeSet };
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::UnGraph
+};
+
+// Upper bound for the label propagation rounds, they stop early once no label changes.
+const MAX_ROUNDS: usize = 1000;
+
+// SplitMix64, enough to shuffle visiting orders and break ties reproducibly.
+struct Random( u64 );
+
+impl Random {
+    fn next( &mut self ) -> u64 {
+        self.0 = self.0.wrapping_add( 0x9E37_79B9_7F4A_7C15 );
+        let mut z = self.0;
+        z = ( z ^ ( z >> 30 ) ).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+        z = ( z ^ ( z >> 27 ) ).wrapping_mul( 0x94D0_49BB_1331_11EB );
+        z ^ ( z >> 31 )
+    }
+
+    fn below( &mut self, bound: usize ) -> usize {
+        ( self.next() % bound as u64 ) as usize
+    }
+
+    fn shuffle<T>( &mut self, values: &mut [T] ) {
+        for i in ( 1..values.len() ).rev() {
+            values.swap( i, self.below( i + 1 ) );
+        }
+    }
+}
+
+// Weighted graph on 0..n with self loops kept apart, one level of the Louvain hierarchy.
+struct Level {
+    adjacency: Vec<BTreeMap<usize, f64>>,
+    loops: Vec<f64>
+}
+
+impl Level {
+    // Self loops count twice towards the degree.
+    fn degrees( &self ) -> Vec<f64> {
+        self.adjacency.iter().zip( self.loops.iter() )
+            .map( |( edges, weight )| edges.values().sum::<f64>() + 2.0 * weight )
+            .collect()
+    }
+
+    // Local moving phase, returns the community of every node and whether anything moved.
+    fn move_nodes( &self, random: &mut Random ) -> ( Vec<usize>, bool ) {
+        let n = self.adjacency.len();
+        let degrees = self.degrees();
+        let total: f64 = degrees.iter().sum();
+        let mut community: Vec<usize> = ( 0..n ).collect();
+        let mut totals = degrees.clone();
+        let mut order: Vec<usize> = ( 0..n ).collect();
+        let mut moved = false;
+        if total <= 0.0 {
+            return ( community, moved );
+        }
+
+        loop {
+            random.shuffle( &mut order );
+            let mut changed = false;
+            for &v in order.iter() {
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for ( &w, &weight ) in self.adjacency[ v ].iter() {
+                    *links.entry( community[ w ] ).or_insert( 0.0 ) += weight;
+                }
+                let current = community[ v ];
+                totals[ current ] -= degrees[ v ];
+                let gain = |c: usize| links.get( &c ).copied().unwrap_or( 0.0 ) - totals[ c ] * degrees[ v ] / total;
+
+                let mut best = ( current, gain( current ) );
+                for &c in links.keys() {
+                    let candidate = gain( c );
+                    if candidate > best.1 + f64::EPSILON {
+                        best = ( c, candidate );
+                    }
+                }
+                totals[ best.0 ] += degrees[ v ];
+                if best.0 != current {
+                    community[ v ] = best.0;
+                    changed = true;
+                    moved = true;
+                }
+            }
+            if !changed {
+                return ( community, moved );
+            }
+        }
+    }
+
+    // Collapses every community into a single node, `community` has to be relabelled to 0..k.
+    fn aggregate( &self, community: &[usize], k: usize ) -> Level {
+        let mut level = Level { adjacency: vec![ BTreeMap::new(); k ], loops: vec![ 0.0; k ] };
+        for ( v, edges ) in self.adjacency.iter().enumerate() {
+            level.loops[ community[ v ] ] += self.loops[ v ];
+            for ( &w, &weight ) in edges.iter() {
+                let ( a, b ) = ( community[ v ], community[ w ] );
+                if a == b {
+                    // Seen from both ends.
+                    level.loops[ a ] += weight / 2.0;
+                } else {
+                    *level.adjacency[ a ].entry( b ).or_insert( 0.0 ) += weight;
+                }
+            }
+        }
+        level
+    }
+}
+
+// Relabels to 0..k in order of first appearance.
+fn relabel( labels: &[usize] ) -> ( Vec<usize>, usize ) {
+    let mut names = BTreeMap::new();
+    let relabelled = labels.iter().map( |&label| {
+        let next = names.len();
+        *names.entry( label ).or_insert( next )
+    } ).collect();
+    ( relabelled, names.len() )
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn level<F>( indexed: &Indexed<'_, I, E>, weight: F ) -> Level
+    where
+        F: Fn( &E ) -> f64
+    {
+        let mut level = Level { adjacency: vec![ BTreeMap::new(); indexed.ids.len() ], loops: vec![ 0.0; indexed.ids.len() ] };
+        for ( v, edges ) in indexed.adjacency.iter().enumerate() {
+            for &( w, edge ) in edges.iter() {
+                if v == w {
+                    level.loops[ v ] += weight( edge );
+                } else {
+                    *level.adjacency[ v ].entry( w ).or_insert( 0.0 ) += weight( edge );
+                }
+            }
+        }
+        level
+    }
+
+    fn partition( indexed: &Indexed<'_, I, E>, labels: &[usize] ) -> Vec<BTreeSet<I>> {
+        let ( labels, k ) = relabel( labels );
+        let mut partition = vec![ BTreeSet::new(); k ];
+        for ( v, &label ) in labels.iter().enumerate() {
+            partition[ label ].insert( indexed.ids[ v ].clone() );
+        }
+        partition
+    }
+
+    // Newman's modularity of `partition`, nodes missing from it count as communities of their own.
+    pub fn modularity<F>( &self, partition: &[BTreeSet<I>], weight: F ) -> f64
+    where
+        F: Fn( &E ) -> f64
+    {
+        let indexed = Indexed::new( self.data() );
+        let level = Self::level( &indexed, weight );
+        let n = indexed.ids.len();
+        let mut labels: Vec<usize> = ( 0..n ).map( |v| partition.len() + v ).collect();
+        for ( c, members ) in partition.iter().enumerate() {
+            members.iter().filter_map( |id| indexed.position( id ) ).for_each( |v| labels[ v ] = c );
+        }
+
+        let degrees = level.degrees();
+        let total: f64 = degrees.iter().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let mut inside: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut sums: BTreeMap<usize, f64> = BTreeMap::new();
+        for v in 0..n {
+            *sums.entry( labels[ v ] ).or_insert( 0.0 ) += degrees[ v ];
+            *inside.entry( labels[ v ] ).or_insert( 0.0 ) += 2.0 * level.loops[ v ] + level.adjacency[ v ].iter()
+                .filter( |( &w, _ )| labels[ w ] == labels[ v ] )
+                .map( |( _, weight )| weight )
+                .sum::<f64>();
+        }
+        sums.iter()
+            .map( |( label, sum )| inside.get( label ).copied().unwrap_or( 0.0 ) / total - ( sum / total ).powi( 2 ) )
+            .sum()
+    }
+
+    // Louvain modularity optimization, nodes are visited in an order shuffled by `seed`. Communities are
+    // sorted by their smallest node.
+    pub fn louvain<F>( &self, weight: F, seed: u64 ) -> Vec<BTreeSet<I>>
+    where
+        F: Fn( &E ) -> f64
+    {
+        let indexed = Indexed::new( self.data() );
+        let mut random = Random( seed );
+        let mut level = Self::level( &indexed, weight );
+        let mut membership: Vec<usize> = ( 0..indexed.ids.len() ).collect();
+        loop {
+            let ( community, moved ) = level.move_nodes( &mut random );
+            if !moved {
+                break;
+            }
+            let ( community, k ) = relabel( &community );
+            membership.iter_mut().for_each( |label| *label = community[ *label ] );
+            level = level.aggregate( &community, k );
+        }
+        Self::partition( &indexed, &membership )
+    }
+
+    // Asynchronous label propagation, every node takes the label with the largest edge weight among
+    // its neighbours. Visiting order and ties follow `seed`, a node keeps its label when it is among
+    // the best. Communities are sorted by their smallest node.
+    pub fn label_propagation<F>( &self, weight: F, seed: u64 ) -> Vec<BTreeSet<I>>
+    where
+        F: Fn( &E ) -> f64
+    {
+        let indexed = Indexed::new( self.data() );
+        let mut random = Random( seed );
+        let level = Self::level( &indexed, weight );
+        let n = indexed.ids.len();
+        let mut labels: Vec<usize> = ( 0..n ).collect();
+        let mut order: Vec<usize> = ( 0..n ).collect();
+
+        for _ in 0..MAX_ROUNDS {
+            random.shuffle( &mut order );
+            let mut changed = false;
+            for &v in order.iter() {
+                let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+                for ( &w, &weight ) in level.adjacency[ v ].iter() {
+                    *weights.entry( labels[ w ] ).or_insert( 0.0 ) += weight;
+                }
+                let Some( best ) = weights.values().copied().reduce( f64::max ) else {
+                    continue;
+                };
+                let ties: Vec<usize> = weights.iter()
+                    .filter( |( _, &weight )| weight >= best - f64::EPSILON )
+                    .map( |( &label, _ )| label )
+                    .collect();
+                if !ties.contains( &labels[ v ] ) {
+                    labels[ v ] = ties[ random.below( ties.len() ) ];
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        Self::partition( &indexed, &labels )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        graph::GraphTraits,
+        undirected_graph::UnGraph
+    };
+
+    // Two four cliques joined by the edge 3 - 4.
+    fn cliques() -> UnGraph<usize, (), f64> {
+        let mut graph = UnGraph::<usize, (), f64>::new();
+        for id in 0..8 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for offset in [ 0, 4 ] {
+            for u in 0..4 {
+                for v in ( u + 1 )..4 {
+                    graph.add_edge( offset + u, offset + v, 1.0 ).unwrap();
+                }
+            }
+        }
+        graph.add_edge( 3, 4, 1.0 ).unwrap();
+        graph
+    }
+
+    fn halves() -> Vec<BTreeSet<usize>> {
+        vec![ BTreeSet::from( [ 0, 1, 2, 3 ] ), BTreeSet::from( [ 4, 5, 6, 7 ] ) ]
+    }
+
+    #[test]
+    fn test_modularity() {
+        let graph = cliques();
+        assert!( ( graph.modularity( &halves(), |weight| *weight ) - ( 12.0 / 13.0 - 0.5 ) ).abs() < 1e-9 );
+        let whole = vec![ ( 0..8 ).collect::<BTreeSet<usize>>() ];
+        assert!( graph.modularity( &whole, |weight| *weight ).abs() < 1e-9 );
+        assert!( graph.modularity( &[], |weight| *weight ) < 0.0 );
+    }
+
+    #[test]
+    fn test_louvain() {
+        let graph = cliques();
+        for seed in 0..10 {
+            assert_eq!( graph.louvain( |weight| *weight, seed ), halves() );
+        }
+
+        // A heavy bridge pulls its ends together.
+        let mut heavy = cliques();
+        *heavy.get_edge_mut( 3, 4 ).unwrap() = 50.0;
+        *heavy.get_edge_mut( 4, 3 ).unwrap() = 50.0;
+        let partition = heavy.louvain( |weight| *weight, 7 );
+        assert!( partition.iter().any( |community| community.contains( &3 ) && community.contains( &4 ) ) );
+    }
+
+    #[test]
+    fn test_label_propagation() {
+        let graph = cliques();
+        for seed in 0..10 {
+            let partition = graph.label_propagation( |weight| *weight, seed );
+            assert_eq!( partition, graph.label_propagation( |weight| *weight, seed ) );
+            assert_eq!( partition.iter().map( BTreeSet::len ).sum::<usize>(), 8 );
+        }
+        assert_eq!( graph.label_propagation( |weight| *weight, 3 ), halves() );
+
+        let mut isolated = UnGraph::<usize, (), f64>::new();
+        isolated.add_node( 1, () ).unwrap();
+        isolated.add_node( 2, () ).unwrap();
+        assert_eq!( isolated.label_propagation( |weight| *weight, 0 ), vec![ BTreeSet::from( [ 1 ] ), BTreeSet::from( [ 2 ] ) ] );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::BTreeSet;
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::{
+        UnGraph,
+        spanning_tree::DisjointSet
+    }
+};
+
+struct LowLink {
+    bridges: Vec<( usize, usize )>,
+    articulation_points: Vec<usize>,
+    biconnected: Vec<Vec<( usize, usize )>>
+}
+
+// Iterative Tarjan low-link DFS, self loops are ignored.
+fn low_link( adjacency: &[Vec<usize>] ) -> LowLink {
+    let n = adjacency.len();
+    let mut counter = 0;
+    let mut discovered = vec![ usize::MAX; n ];
+    let mut low = vec![ 0; n ];
+    let mut parent = vec![ usize::MAX; n ];
+    let mut articulation = vec![ false; n ];
+    let mut edges = Vec::new();
+    let mut result = LowLink { bridges: Vec::new(), articulation_points: Vec::new(), biconnected: Vec::new() };
+
+    for root in 0..n {
+        if discovered[ root ] != usize::MAX {
+            continue;
+        }
+        discovered[ root ] = counter;
+        low[ root ] = counter;
+        counter += 1;
+        let mut children = 0;
+        let mut stack = vec![ ( root, 0 ) ];
+
+        while let Some( frame ) = stack.last_mut() {
+            let v = frame.0;
+            if let Some( &w ) = adjacency[ v ].get( frame.1 ) {
+                frame.1 += 1;
+                if w == v {
+                    continue;
+                }
+                if discovered[ w ] == usize::MAX {
+                    discovered[ w ] = counter;
+                    low[ w ] = counter;
+                    counter += 1;
+                    parent[ w ] = v;
+                    edges.push( ( v, w ) );
+                    stack.push( ( w, 0 ) );
+                    if v == root {
+                        children += 1;
+                    }
+                } else if w != parent[ v ] && discovered[ w ] < discovered[ v ] {
+                    low[ v ] = low[ v ].min( discovered[ w ] );
+                    edges.push( ( v, w ) );
+                }
+                continue;
+            }
+
+            stack.pop();
+            let w = v;
+            let v = parent[ w ];
+            if v == usize::MAX {
+                continue;
+            }
+            low[ v ] = low[ v ].min( low[ w ] );
+            if low[ w ] > discovered[ v ] {
+                result.bridges.push( ( v.min( w ), v.max( w ) ) );
+            }
+            if low[ w ] >= discovered[ v ] {
+                if v != root {
+                    articulation[ v ] = true;
+                }
+                let mut component = Vec::new();
+                while let Some( edge ) = edges.pop() {
+                    component.push( ( edge.0.min( edge.1 ), edge.0.max( edge.1 ) ) );
+                    if edge == ( v, w ) {
+                        break;
+                    }
+                }
+                result.biconnected.push( component );
+            }
+        }
+        if children > 1 {
+            articulation[ root ] = true;
+        }
+    }
+    result.articulation_points = ( 0..n ).filter( |&v| articulation[ v ] ).collect();
+    result
+}
+
+// Edges of every biconnected component as ( smaller, larger ) node pairs.
+pub(crate) fn biconnected( adjacency: &[Vec<usize>] ) -> Vec<Vec<( usize, usize )>> {
+    low_link( adjacency ).biconnected
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn edge_ids( indexed: &Indexed<'_, I, E>, ( u, v ): ( usize, usize ) ) -> ( I, I ) {
+        ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() )
+    }
+
+    // Edges whose removal disconnects their endpoints, as ( smaller id, larger id ).
+    pub fn bridges( &self ) -> Vec<( I, I )> {
+        let indexed = Indexed::new( self.data() );
+        low_link( &indexed.neighbors() ).bridges.into_iter()
+            .map( |edge| Self::edge_ids( &indexed, edge ) )
+            .collect()
+    }
+
+    pub fn articulation_points( &self ) -> BTreeSet<I> {
+        let indexed = Indexed::new( self.data() );
+        low_link( &indexed.neighbors() ).articulation_points.into_iter()
+            .map( |v| indexed.ids[ v ].clone() )
+            .collect()
+    }
+
+    pub fn biconnected_components( &self ) -> Vec<BTreeSet<( I, I )>> {
+        let indexed = Indexed::new( self.data() );
+        biconnected( &indexed.neighbors() ).into_iter()
+            .map( |component| component.into_iter().map( |edge| Self::edge_ids( &indexed, edge ) ).collect() )
+            .collect()
+    }
+
+    // Connected components left over once every bridge is removed.
+    pub fn two_edge_connected_components( &self ) -> Vec<BTreeSet<I>> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let bridges: BTreeSet<( usize, usize )> = low_link( &adjacency ).bridges.into_iter().collect();
+        let mut sets = DisjointSet::new( adjacency.len() );
+        for ( u, edges ) in adjacency.iter().enumerate() {
+            for &v in edges.iter() {
+                if !bridges.contains( &( u.min( v ), u.max( v ) ) ) {
+                    sets.union( u, v );
+                }
+            }
+        }
+
+        let mut components: Vec<BTreeSet<I>> = Vec::new();
+        let mut component_of = vec![ usize::MAX; adjacency.len() ];
+        for v in 0..adjacency.len() {
+            let root = sets.find( v );
+            if component_of[ root ] == usize::MAX {
+                component_of[ root ] = components.len();
+                components.push( BTreeSet::new() );
+            }
+            components[ component_of[ root ] ].insert( indexed.ids[ v ].clone() );
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        graph::GraphTraits,
+        undirected_graph::UnGraph
+    };
+
+    // Two triangles joined through the bridge 3 - 4, with a pendant node 7 hanging off 6.
+    fn graph() -> UnGraph<usize, (), ()> {
+        let mut graph = UnGraph::<usize, (), ()>::new();
+        for id in 1..=7 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v ) in [ ( 1, 2 ), ( 2, 3 ), ( 3, 1 ), ( 3, 4 ), ( 4, 5 ), ( 5, 6 ), ( 6, 4 ), ( 6, 7 ) ] {
+            graph.add_edge( u, v, () ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_bridges() {
+        let mut bridges = graph().bridges();
+        bridges.sort();
+        assert_eq!( bridges, vec![ ( 3, 4 ), ( 6, 7 ) ] );
+    }
+
+    #[test]
+    fn test_articulation_points() {
+        assert_eq!( graph().articulation_points(), BTreeSet::from( [ 3, 4, 6 ] ) );
+
+        let mut star = UnGraph::<usize, (), ()>::new();
+        for id in 1..=4 {
+            star.add_node( id, () ).unwrap();
+        }
+        for id in 2..=4 {
+            star.add_edge( 1, id, () ).unwrap();
+        }
+        assert_eq!( star.articulation_points(), BTreeSet::from( [ 1 ] ) );
+    }
+
+    #[test]
+    fn test_biconnected_components() {
+        let mut components = graph().biconnected_components();
+        components.sort();
+        assert_eq!( components, vec![
+            BTreeSet::from( [ ( 1, 2 ), ( 1, 3 ), ( 2, 3 ) ] ),
+            BTreeSet::from( [ ( 3, 4 ) ] ),
+            BTreeSet::from( [ ( 4, 5 ), ( 4, 6 ), ( 5, 6 ) ] ),
+            BTreeSet::from( [ ( 6, 7 ) ] )
+        ] );
+    }
+
+    #[test]
+    fn test_two_edge_connected_components() {
+        let mut components = graph().two_edge_connected_components();
+        components.sort();
+        assert_eq!( components, vec![ BTreeSet::from( [ 1, 2, 3 ] ), BTreeSet::from( [ 4, 5, 6 ] ), BTreeSet::from( [ 7 ] ) ] );
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::BTreeSet;
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::{
+        UnGraph,
+        coloring::simple
+    }
+};
+
+fn alive_degree( adjacency: &[Vec<usize>], alive: &[bool], v: usize ) -> usize {
+    adjacency[ v ].iter().filter( |&&w| alive[ w ] ).count()
+}
+
+// Removes the given nodes that are still alive and returns them so the caller can restore them.
+fn remove( alive: &mut [bool], nodes: impl Iterator<Item = usize> ) -> Vec<usize> {
+    nodes.filter( |&v| std::mem::replace( &mut alive[ v ], false ) ).collect()
+}
+
+struct IndependentSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    alive: Vec<bool>,
+    current: Vec<usize>,
+    best: Vec<usize>
+}
+
+impl IndependentSearch<'_> {
+    fn take( &mut self, v: usize ) {
+        let adjacency = self.adjacency;
+        let removed = remove( &mut self.alive, std::iter::once( v ).chain( adjacency[ v ].iter().copied() ) );
+        self.current.push( v );
+        self.search();
+        self.current.pop();
+        removed.into_iter().for_each( |w| self.alive[ w ] = true );
+    }
+
+    // Nodes of degree at most one are always safe to take, otherwise branch on a node of maximum
+    // degree and prune once the remaining nodes cannot beat the best set.
+    fn search( &mut self ) {
+        let remaining: Vec<usize> = ( 0..self.alive.len() ).filter( |&v| self.alive[ v ] ).collect();
+        if self.current.len() + remaining.len() <= self.best.len() {
+            return;
+        }
+        let Some( &low ) = remaining.iter().min_by_key( |&&v| alive_degree( self.adjacency, &self.alive, v ) ) else {
+            self.best = self.current.clone();
+            return;
+        };
+        if alive_degree( self.adjacency, &self.alive, low ) <= 1 {
+            self.take( low );
+            return;
+        }
+
+        let Some( &high ) = remaining.iter().max_by_key( |&&v| alive_degree( self.adjacency, &self.alive, v ) ) else {
+            return;
+        };
+        self.take( high );
+        self.alive[ high ] = false;
+        self.search();
+        self.alive[ high ] = true;
+    }
+}
+
+struct DominatingSearch<'a> {
+    // Closed neighbourhoods, every node dominates itself.
+    closed: &'a [Vec<usize>],
+    largest: usize,
+    covered: Vec<usize>,
+    uncovered: usize,
+    forbidden: Vec<bool>,
+    current: Vec<usize>,
+    best: Vec<usize>
+}
+
+impl DominatingSearch<'_> {
+    fn choose( &mut self, v: usize ) {
+        for &w in self.closed[ v ].iter() {
+            if self.covered[ w ] == 0 {
+                self.uncovered -= 1;
+            }
+            self.covered[ w ] += 1;
+        }
+        self.current.push( v );
+    }
+
+    fn unchoose( &mut self, v: usize ) {
+        for &w in self.closed[ v ].iter() {
+            self.covered[ w ] -= 1;
+            if self.covered[ w ] == 0 {
+                self.uncovered += 1;
+            }
+        }
+        self.current.pop();
+    }
+
+    // Some node of the closed neighbourhood of an undominated node has to be chosen, branch over
+    // those with the fewest candidates first. Nodes tried in earlier branches are excluded later on.
+    fn search( &mut self ) {
+        if self.uncovered == 0 {
+            if self.current.len() < self.best.len() {
+                self.best = self.current.clone();
+            }
+            return;
+        }
+        if self.current.len() + self.uncovered.div_ceil( self.largest ) >= self.best.len() {
+            return;
+        }
+        let candidates = |u: usize| self.closed[ u ].iter().copied().filter( |&v| !self.forbidden[ v ] );
+        let Some( u ) = ( 0..self.closed.len() )
+            .filter( |&u| self.covered[ u ] == 0 )
+            .min_by_key( |&u| candidates( u ).count() ) else {
+            return;
+        };
+        let branches: Vec<usize> = candidates( u ).collect();
+
+        for &v in branches.iter() {
+            self.choose( v );
+            self.search();
+            self.unchoose( v );
+            self.forbidden[ v ] = true;
+        }
+        branches.into_iter().for_each( |v| self.forbidden[ v ] = false );
+    }
+}
+
+fn closed_neighborhoods( adjacency: &[Vec<usize>] ) -> Vec<Vec<usize>> {
+    adjacency.iter().enumerate()
+        .map( |( v, edges )| std::iter::once( v ).chain( edges.iter().copied() ).collect() )
+        .collect()
+}
+
+fn greedy_dominating( closed: &[Vec<usize>] ) -> Vec<usize> {
+    let mut covered = vec![ false; closed.len() ];
+    let mut chosen = Vec::new();
+    loop {
+        let gain = |v: usize| closed[ v ].iter().filter( |&&w| !covered[ w ] ).count();
+        let Some( v ) = ( 0..closed.len() ).filter( |&v| gain( v ) > 0 ).min_by_key( |&v| ( std::cmp::Reverse( gain( v ) ), v ) ) else {
+            return chosen;
+        };
+        closed[ v ].iter().for_each( |&w| covered[ w ] = true );
+        chosen.push( v );
+    }
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    fn id_set( indexed: &Indexed<'_, I, E>, nodes: impl IntoIterator<Item = usize> ) -> BTreeSet<I> {
+        nodes.into_iter().map( |v| indexed.ids[ v ].clone() ).collect()
+    }
+
+    fn has_loop( &self, id: &I ) -> bool {
+        self.data().get( id ).is_some_and( |node| node.adjacencies().contains_key( id ) )
+    }
+
+    // Exact branch and bound, exponential in the worst case. Nodes with a self loop are never independent.
+    pub fn maximum_independent_set( &self ) -> BTreeSet<I> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        let alive = indexed.ids.iter().map( |id| !self.has_loop( id ) ).collect();
+        let mut search = IndependentSearch { adjacency: &adjacency, alive, current: Vec::new(), best: Vec::new() };
+        search.search();
+        Self::id_set( &indexed, search.best )
+    }
+
+    // Repeatedly takes a node of minimum remaining degree and drops its neighbours.
+    pub fn greedy_independent_set( &self ) -> BTreeSet<I> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        let mut alive: Vec<bool> = indexed.ids.iter().map( |id| !self.has_loop( id ) ).collect();
+        let mut chosen = Vec::new();
+        while let Some( v ) = ( 0..alive.len() ).filter( |&v| alive[ v ] ).min_by_key( |&v| alive_degree( &adjacency, &alive, v ) ) {
+            remove( &mut alive, std::iter::once( v ).chain( adjacency[ v ].iter().copied() ) );
+            chosen.push( v );
+        }
+        Self::id_set( &indexed, chosen )
+    }
+
+    // Complement of a maximum independent set.
+    pub fn minimum_vertex_cover( &self ) -> BTreeSet<I> {
+        let independent = self.maximum_independent_set();
+        self.data().keys().filter( |id| !independent.contains( id ) ).cloned().collect()
+    }
+
+    // Both ends of a maximal matching, at most twice the size of a minimum cover.
+    pub fn approximate_vertex_cover( &self ) -> BTreeSet<I> {
+        let mut cover: BTreeSet<I> = self.data().keys().filter( |id| self.has_loop( id ) ).cloned().collect();
+        for ( id, node ) in self.data().iter() {
+            for neighbor in node.adjacencies().keys() {
+                if !cover.contains( id ) && !cover.contains( neighbor ) {
+                    cover.insert( id.clone() );
+                    cover.insert( neighbor.clone() );
+                }
+            }
+        }
+        cover
+    }
+
+    // Exact branch and bound seeded with the greedy solution, exponential in the worst case.
+    pub fn minimum_dominating_set( &self ) -> BTreeSet<I> {
+        let indexed = Indexed::new( self.data() );
+        let closed = closed_neighborhoods( &simple( &indexed ) );
+        let n = closed.len();
+        let mut search = DominatingSearch {
+            closed: &closed,
+            largest: closed.iter().map( Vec::len ).max().unwrap_or( 1 ),
+            covered: vec![ 0; n ],
+            uncovered: n,
+            forbidden: vec![ false; n ],
+            current: Vec::new(),
+            best: greedy_dominating( &closed )
+        };
+        search.search();
+        Self::id_set( &indexed, search.best )
+    }
+
+    // Repeatedly takes the node that dominates the most undominated nodes, within a ln( n ) factor.
+    pub fn greedy_dominating_set( &self ) -> BTreeSet<I> {
+        let indexed = Indexed::new( self.data() );
+        Self::id_set( &indexed, greedy_dominating( &closed_neighborhoods( &simple( &indexed ) ) ) )
+    }
+
+    // No edge, self loops included, joins two members of `nodes`.
+    pub fn is_independent_set( &self, nodes: &BTreeSet<I> ) -> bool {
+        nodes.iter().all( |id| self.data().get( id ).is_some_and( |node| node.adjacencies().keys()
+            .all( |neighbor| !nodes.contains( neighbor ) )
+        ) )
+    }
+
+    // Every edge, self loops included, has at least one end in `nodes`.
+    pub fn is_vertex_cover( &self, nodes: &BTreeSet<I> ) -> bool {
+        self.data().iter().all( |( id, node )| nodes.contains( id ) || node.adjacencies().keys()
+            .all( |neighbor| nodes.contains( neighbor ) )
+        )
+    }
+
+    // Every node is in `nodes` or adjacent to one of them.
+    pub fn is_dominating_set( &self, nodes: &BTreeSet<I> ) -> bool {
+        nodes.iter().all( |id| self.data().contains_key( id ) )
+            && self.data().iter().all( |( id, node )| nodes.contains( id ) || node.adjacencies().keys()
+                .any( |neighbor| nodes.contains( neighbor ) )
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        graph::{
+            GraphTraits,
+            fixtures::petersen
+        },
+        undirected_graph::UnGraph
+    };
+
+    fn path( n: usize ) -> UnGraph<usize, (), ()> {
+        let mut graph = UnGraph::<usize, (), ()>::new();
+        for id in 0..n {
+            graph.add_node( id, () ).unwrap();
+        }
+        for id in 1..n {
+            graph.add_edge( id - 1, id, () ).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_independent_set() {
+        let graph = petersen();
+        let independent = graph.maximum_independent_set();
+        assert_eq!( independent.len(), 4 );
+        assert!( graph.is_independent_set( &independent ) );
+        let greedy = graph.greedy_independent_set();
+        assert!( graph.is_independent_set( &greedy ) );
+        assert!( greedy.len() <= 4 );
+        assert!( !graph.is_independent_set( &BTreeSet::from( [ 0, 1 ] ) ) );
+
+        assert_eq!( path( 7 ).maximum_independent_set(), BTreeSet::from( [ 0, 2, 4, 6 ] ) );
+    }
+
+    #[test]
+    fn test_vertex_cover() {
+        let graph = petersen();
+        let cover = graph.minimum_vertex_cover();
+        assert_eq!( cover.len(), 6 );
+        assert!( graph.is_vertex_cover( &cover ) );
+        let approximate = graph.approximate_vertex_cover();
+        assert!( graph.is_vertex_cover( &approximate ) );
+        assert!( approximate.len() <= 12 );
+        assert!( !graph.is_vertex_cover( &BTreeSet::from( [ 0, 1, 2, 3, 4 ] ) ) );
+
+        let mut looped = path( 3 );
+        looped.add_node( 3, () ).unwrap();
+        let _ = looped.add_edge( 3, 3, () );
+        assert_eq!( looped.minimum_vertex_cover(), BTreeSet::from( [ 1, 3 ] ) );
+        assert!( looped.is_vertex_cover( &looped.approximate_vertex_cover() ) );
+    }
+
+    #[test]
+    fn test_dominating_set() {
+        let graph = petersen();
+        let dominating = graph.minimum_dominating_set();
+        assert_eq!( dominating.len(), 3 );
+        assert!( graph.is_dominating_set( &dominating ) );
+        assert!( graph.is_dominating_set( &graph.greedy_dominating_set() ) );
+        assert!( !graph.is_dominating_set( &BTreeSet::from( [ 0, 1 ] ) ) );
+
+        let path = path( 9 );
+        assert_eq!( path.minimum_dominating_set().len(), 3 );
+        assert_eq!( path.minimum_dominating_set(), BTreeSet::from( [ 1, 4, 7 ] ) );
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed,
+        metrics::Distance,
+        weight::Weight
+    },
+    undirected_graph::UnGraph
+};
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    pub fn has_cycle( &self ) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    // The edge back to the DFS parent is the same undirected edge and does not close a cycle,
+    // any other edge to a node on the current path does.
+    pub fn find_cycle( &self ) -> Option<Vec<I>> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = indexed.neighbors();
+        let n = adjacency.len();
+        let mut parent = vec![ usize::MAX; n ];
+        let mut on_path = vec![ false; n ];
+        let mut visited = vec![ false; n ];
+
+        for root in 0..n {
+            if visited[ root ] {
+                continue;
+            }
+            visited[ root ] = true;
+            on_path[ root ] = true;
+            let mut stack = vec![ ( root, 0 ) ];
+
+            while let Some( frame ) = stack.last_mut() {
+                let v = frame.0;
+                let Some( &w ) = adjacency[ v ].get( frame.1 ) else {
+                    on_path[ v ] = false;
+                    stack.pop();
+                    continue;
+                };
+                frame.1 += 1;
+
+                if w == v {
+                    return Some( vec![ indexed.ids[ v ].clone() ] );
+                } else if !visited[ w ] {
+                    visited[ w ] = true;
+                    on_path[ w ] = true;
+                    parent[ w ] = v;
+                    stack.push( ( w, 0 ) );
+                } else if on_path[ w ] && parent[ v ] != w {
+                    let mut cycle = vec![ v ];
+                    let mut current = v;
+                    while current != w {
+                        current = parent[ current ];
+                        cycle.push( current );
+                    }
+                    cycle.reverse();
+                    return Some( cycle.into_iter().map( |i| indexed.ids[ i ].clone() ).collect() );
+                }
+            }
+        }
+        None
+    }
+
+    pub fn girth( &self ) -> Distance<usize> {
+        self.girth_by( |_| 1usize )
+    }
+
+    // Length of the shortest cycle, a self loop counts on its own. Every edge is dropped in turn and
+    // its ends joined by the shortest path around it. Infinite for forests. Weights must be non-negative,
+    // cycles through edges with negative weights are not counted.
+    pub fn girth_by<W, F>( &self, weight: F ) -> Distance<W>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let mut girth = None;
+        for ( u, node ) in self.data().iter() {
+            for ( v, edge ) in node.adjacencies().iter().filter( |( v, _ )| u <= *v ) {
+                let closing = weight( edge );
+                if closing < W::zero() {
+                    continue;
+                }
+                let around = if u == v {
+                    Some( W::zero() )
+                } else {
+                    let ( paths, reached ) = self.data().dijkstra(
+                        u.clone(),
+                        |from, to, edge| ( ( from, to ) != ( u, v ) && ( from, to ) != ( v, u ) ).then( || weight( edge ) ),
+                        |id| id == v
+                    );
+                    reached.and_then( |v| paths.distance( &v ).cloned() )
+                };
+                if let Some( length ) = around.map( |around| around + closing ) {
+                    if girth.as_ref().is_none_or( |girth| length < *girth ) {
+                        girth = Some( length );
+                    }
+                }
+            }
+        }
+        Distance::from( girth )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{ GraphTraits, metrics::Distance },
+        undirected_graph::UnGraph
+    };
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = UnGraph::<usize, (), ()>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, () ).unwrap();
+        graph.add_edge( 2, 3, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+        graph.add_edge( 2, 5, () ).unwrap();
+        assert!( !graph.has_cycle() );
+        assert!( graph.find_cycle().is_none() );
+
+        graph.add_edge( 4, 5, () ).unwrap();
+        assert!( graph.has_cycle() );
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!( cycle.len(), 4 );
+        for ( i, id ) in cycle.iter().enumerate() {
+            assert!( graph.contains_edge( *id, cycle[ ( i + 1 ) % cycle.len() ] ) );
+        }
+    }
+
+    #[test]
+    fn test_find_cycle_disconnected() {
+        let mut graph = UnGraph::<usize, (), ()>::new();
+        for id in 1..=5 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, () ).unwrap();
+        graph.add_edge( 3, 4, () ).unwrap();
+        graph.add_edge( 4, 5, () ).unwrap();
+        graph.add_edge( 5, 3, () ).unwrap();
+        assert_eq!( graph.find_cycle().map( |cycle| cycle.len() ), Some( 3 ) );
+    }
+
+    #[test]
+    fn test_girth() {
+        let mut graph = UnGraph::<usize, (), u32>::new();
+        for id in 1..=6 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 4, 1 ), ( 5, 6, 1 ) ] {
+            graph.add_edge( u, v, weight ).unwrap();
+        }
+        assert_eq!( graph.girth(), Distance::Infinite );
+
+        // A light square 1 - 2 - 3 - 4 split by the heavy chord 1 - 3.
+        graph.add_edge( 4, 1, 1 ).unwrap();
+        graph.add_edge( 1, 3, 5 ).unwrap();
+        assert_eq!( graph.girth(), Distance::Finite( 3 ) );
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 4 ) );
+
+        let _ = graph.add_edge( 6, 6, 2 );
+        assert_eq!( graph.girth(), Distance::Finite( 1 ) );
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 2 ) );
+    }
+
+    #[test]
+    fn test_girth_negative_weights() {
+        let mut graph = UnGraph::<usize, (), i32>::new();
+        for id in 1..=4 {
+            graph.add_node( id, () ).unwrap();
+        }
+        for ( u, v, weight ) in [ ( 1, 2, 1 ), ( 2, 3, -5 ), ( 3, 1, 1 ) ] {
+            graph.add_edge( u, v, weight ).unwrap();
+        }
+        let _ = graph.add_edge( 4, 4, -1 );
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Infinite );
+        graph.add_edge( 2, 4, 1 ).unwrap();
+        graph.add_edge( 4, 3, 1 ).unwrap();
+        assert_eq!( graph.girth_by( |weight| *weight ), Distance::Finite( 4 ) );
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2024 Bewusstsein Labs
+
+use crate::{
+    graph::{
+        GraphAccess,
+        TooManyOddNodes,
+        indexed::Indexed,
+        weight::Weight
+    },
+    undirected_graph::UnGraph
+};
+
+// Most odd degree nodes the Chinese postman pairs up exactly, 2^20 matching states.
+pub const CHINESE_POSTMAN_ODD_NODES: usize = 20;
+
+// Closed walk as a list of edges together with its total weight.
+pub type PostmanRoute<I, W> = ( Vec<( I, I )>, W );
+
+// Hierholzer over an edge list, parallel edges and self loops are allowed. Returns the node walk.
+fn hierholzer( n: usize, edges: &[( usize, usize )], start: usize ) -> Vec<usize> {
+    let mut incident = vec![ Vec::new(); n ];
+    for ( id, &( u, v ) ) in edges.iter().enumerate() {
+        incident[ u ].push( id );
+        if u != v {
+            incident[ v ].push( id );
+        }
+    }
+    let mut used = vec![ false; edges.len() ];
+    let mut next = vec![ 0; n ];
+    let mut stack = vec![ start ];
+    let mut walk = Vec::new();
+
+    while let Some( &v ) = stack.last() {
+        while incident[ v ].get( next[ v ] ).is_some_and( |&id| used[ id ] ) {
+            next[ v ] += 1;
+        }
+        if let Some( &id ) = incident[ v ].get( next[ v ] ) {
+            used[ id ] = true;
+            let ( a, b ) = edges[ id ];
+            stack.push( if a == v { b } else { a } );
+        } else {
+            walk.push( v );
+            stack.pop();
+        }
+    }
+    walk.reverse();
+    walk
+}
+
+fn degrees( n: usize, edges: &[( usize, usize )] ) -> Vec<usize> {
+    let mut degrees = vec![ 0; n ];
+    for &( u, v ) in edges.iter() {
+        degrees[ u ] += 1;
+        degrees[ v ] += 1;
+    }
+    degrees
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Every undirected edge once as ( u, v ) with u <= v.
+    fn edge_list( indexed: &Indexed<'_, I, E> ) -> Vec<( usize, usize )> {
+        indexed.adjacency.iter()
+            .enumerate()
+            .flat_map( |( u, edges )| edges.iter().filter( move |&&( v, _ )| u <= v ).map( move |&( v, _ )| ( u, v ) ) )
+            .collect()
+    }
+
+    fn walk_edges( indexed: &Indexed<'_, I, E>, edges: &[( usize, usize )], start: usize ) -> Option<Vec<( I, I )>> {
+        if edges.is_empty() {
+            return Some( Vec::new() );
+        }
+        let walk = hierholzer( indexed.ids.len(), edges, start );
+        ( walk.len() == edges.len() + 1 ).then( || walk.windows( 2 )
+            .map( |pair| ( indexed.ids[ pair[ 0 ] ].clone(), indexed.ids[ pair[ 1 ] ].clone() ) )
+            .collect()
+        )
+    }
+
+    fn euler( &self, closed: bool ) -> Option<Vec<( I, I )>> {
+        let indexed = Indexed::new( self.data() );
+        let edges = Self::edge_list( &indexed );
+        let degrees = degrees( indexed.ids.len(), &edges );
+        let odd: Vec<usize> = ( 0..degrees.len() ).filter( |&v| degrees[ v ] % 2 == 1 ).collect();
+        let start = match odd.len() {
+            0 => ( 0..degrees.len() ).find( |&v| degrees[ v ] > 0 ).unwrap_or( 0 ),
+            2 if !closed => odd[ 0 ],
+            _ => return None
+        };
+        Self::walk_edges( &indexed, &edges, start )
+    }
+
+    pub fn eulerian_circuit( &self ) -> Option<Vec<( I, I )>> {
+        self.euler( true )
+    }
+
+    pub fn eulerian_path( &self ) -> Option<Vec<( I, I )>> {
+        self.euler( false )
+    }
+
+    pub fn is_eulerian( &self ) -> bool {
+        self.eulerian_circuit().is_some()
+    }
+
+    pub fn has_eulerian_path( &self ) -> bool {
+        self.eulerian_path().is_some()
+    }
+
+    // Shortest closed walk using every edge at least once, None when the edges are not connected. Odd
+    // nodes are paired by an exact minimum-weight matching over subsets, which is exponential in their
+    // number, so more than `CHINESE_POSTMAN_ODD_NODES` of them give `TooManyOddNodes`.
+    pub fn chinese_postman<W, F>( &self, weight: F ) -> Result<Option<PostmanRoute<I, W>>, TooManyOddNodes>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let indexed = Indexed::new( self.data() );
+        let edges = Self::edge_list( &indexed );
+        let degrees = degrees( indexed.ids.len(), &edges );
+        let odd: Vec<usize> = ( 0..degrees.len() ).filter( |&v| degrees[ v ] % 2 == 1 ).collect();
+        if odd.len() > CHINESE_POSTMAN_ODD_NODES {
+            return Err( TooManyOddNodes( odd.len() ) );
+        }
+        Ok( self.postman( &indexed, edges, &degrees, &odd, weight ) )
+    }
+
+    fn postman<W, F>( &self, indexed: &Indexed<'_, I, E>, mut edges: Vec<( usize, usize )>, degrees: &[usize], odd: &[usize], weight: F ) -> Option<PostmanRoute<I, W>>
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let mut total = edges.iter()
+            .filter_map( |&( u, v )| self.data().get_edge( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() ) )
+            .fold( W::zero(), |total, edge| total + weight( edge ) );
+
+        let paths: Vec<_> = odd.iter()
+            .map( |&v| self.data().dijkstra( indexed.ids[ v ].clone(), |_, _, edge| Some( weight( edge ) ), |_| false ).0 )
+            .collect();
+        let distance = |i: usize, j: usize| paths[ i ].distance( &indexed.ids[ odd[ j ] ] ).cloned();
+
+        let k = odd.len();
+        let full = ( 1usize << k ) - 1;
+        let mut best: Vec<Option<( W, usize )>> = vec![ None; 1 << k ];
+        best[ 0 ] = Some( ( W::zero(), usize::MAX ) );
+        for mask in 0..full {
+            let Some( ( cost, _ ) ) = best[ mask ].clone() else {
+                continue;
+            };
+            let i = ( !mask ).trailing_zeros() as usize;
+            for j in ( i + 1 )..k {
+                if mask & ( 1 << j ) != 0 {
+                    continue;
+                }
+                let Some( pair ) = distance( i, j ) else {
+                    continue;
+                };
+                let next = mask | ( 1 << i ) | ( 1 << j );
+                let cost = cost.clone() + pair;
+                if best[ next ].as_ref().is_none_or( |( best, _ )| cost < *best ) {
+                    best[ next ] = Some( ( cost, mask ) );
+                }
+            }
+        }
+
+        let mut mask = full;
+        while mask != 0 {
+            let ( _, previous ) = best[ mask ].clone()?;
+            let pair = mask & !previous;
+            let ( i, j ) = ( pair.trailing_zeros() as usize, ( usize::BITS - 1 - pair.leading_zeros() ) as usize );
+            let path = paths[ i ].path_to( &indexed.ids[ odd[ j ] ] )?;
+            total = total + distance( i, j )?;
+            for step in path.windows( 2 ) {
+                let ( u, v ) = ( indexed.position( &step[ 0 ] )?, indexed.position( &step[ 1 ] )? );
+                edges.push( ( u.min( v ), u.max( v ) ) );
+            }
+            mask = previous;
+        }
+
+        let start = ( 0..indexed.ids.len() ).find( |&v| degrees[ v ] > 0 ).unwrap_or( 0 );
+        Self::walk_edges( indexed, &edges, start ).map( |walk| ( walk, total ) )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{ GraphTraits, TooManyOddNodes, fixtures::weighted_ungraph },
+        undirected_graph::UnGraph
+    };
+
+    fn assert_walk( graph: &UnGraph<usize, (), u32>, walk: &[ ( usize, usize ) ] ) {
+        for pair in walk.windows( 2 ) {
+            assert_eq!( pair[ 0 ].1, pair[ 1 ].0 );
+        }
+        for &( u, v ) in walk.iter() {
+            assert!( graph.contains_edge( u, v ) );
+        }
+    }
+
+    #[test]
+    fn test_eulerian_circuit() {
+        let graph = weighted_ungraph( 6, &[ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 1, 1 ), ( 3, 4, 1 ), ( 4, 5, 1 ), ( 5, 3, 1 ) ] );
+        assert!( graph.is_eulerian() );
+        let circuit = graph.eulerian_circuit().unwrap();
+        assert_eq!( circuit.len(), 6 );
+        assert_eq!( circuit.first().unwrap().0, circuit.last().unwrap().1 );
+        assert_walk( &graph, &circuit );
+    }
+
+    #[test]
+    fn test_eulerian_path() {
+        let graph = weighted_ungraph( 5, &[ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 1, 1 ), ( 3, 4, 1 ) ] );
+        assert!( !graph.is_eulerian() );
+        assert!( graph.has_eulerian_path() );
+        let path = graph.eulerian_path().unwrap();
+        assert_eq!( path.len(), 4 );
+        assert_walk( &graph, &path );
+
+        let disconnected = weighted_ungraph( 7, &[ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 1, 1 ), ( 4, 5, 1 ), ( 5, 6, 1 ), ( 6, 4, 1 ) ] );
+        assert!( !disconnected.has_eulerian_path() );
+    }
+
+    #[test]
+    fn test_chinese_postman() {
+        // A square with one diagonal, nodes 1 and 3 are odd and joined by the diagonal.
+        let graph = weighted_ungraph( 5, &[ ( 1, 2, 3 ), ( 2, 3, 4 ), ( 3, 4, 5 ), ( 4, 1, 6 ), ( 1, 3, 2 ) ] );
+        let ( route, weight ) = graph.chinese_postman( |weight| *weight ).unwrap().unwrap();
+        assert_eq!( weight, 22 );
+        assert_eq!( route.len(), 6 );
+        assert_eq!( route.first().unwrap().0, route.last().unwrap().1 );
+        assert_walk( &graph, &route );
+
+        let eulerian = weighted_ungraph( 4, &[ ( 1, 2, 1 ), ( 2, 3, 1 ), ( 3, 1, 1 ) ] );
+        assert_eq!( eulerian.chinese_postman( |weight| *weight ).unwrap().map( |( _, weight )| weight ), Some( 3 ) );
+    }
+
+    #[test]
+    fn test_chinese_postman_odd_nodes() {
+        // Every leaf of a star is odd, so every edge has to be walked twice.
+        let star: Vec<( usize, usize, u32 )> = ( 1..=16 ).map( |leaf| ( 0, leaf, leaf as u32 ) ).collect();
+        let graph = weighted_ungraph( 17, &star );
+        let ( route, weight ) = graph.chinese_postman( |weight| *weight ).unwrap().unwrap();
+        assert_eq!( weight, 2 * ( 1..=16 ).sum::<u32>() );
+        assert_eq!( route.len(), 32 );
+        assert_walk( &graph, &route );
+
+        let star: Vec<( usize, usize, u32 )> = ( 1..=70 ).map( |leaf| ( 0, leaf, 1 ) ).collect();
+        assert_eq!( weighted_ungraph( 71, &star ).chinese_postman( |weight| *weight ), Err( TooManyOddNodes( 70 ) ) );
+    }
+}
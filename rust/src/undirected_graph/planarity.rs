@@ -0,0 +1,878 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::collections::{ BTreeMap, BTreeSet };
+
+use thiserror::Error;
+
+use crate::{
+    graph::{
+        GraphAccess,
+        indexed::Indexed
+    },
+    undirected_graph::{
+        UnGraph,
+        coloring::simple,
+        components::biconnected
+    }
+};
+
+// Clockwise order of the neighbours around every node, self loops left out.
+#[derive( Debug, Clone, PartialEq, Eq )]
+pub struct Embedding<I> {
+    rotation: BTreeMap<I, Vec<I>>
+}
+
+impl<I> Embedding<I>
+where
+    I: Clone + Ord
+{
+    pub fn neighbors( &self, node: &I ) -> Option<&[I]> {
+        self.rotation.get( node ).map( Vec::as_slice )
+    }
+
+    pub fn rotation( &self ) -> &BTreeMap<I, Vec<I>> {
+        &self.rotation
+    }
+
+    // Every face as the nodes along its boundary, each edge is walked once in either direction. After
+    // the edge u -> v the walk turns to the neighbour of v just counterclockwise of u.
+    pub fn faces( &self ) -> Vec<Vec<I>> {
+        let mut position = BTreeMap::new();
+        for ( v, neighbors ) in self.rotation.iter() {
+            for ( i, w ) in neighbors.iter().enumerate() {
+                position.insert( ( v, w ), i );
+            }
+        }
+        let mut visited = BTreeSet::new();
+        let mut faces = Vec::new();
+        for ( v, neighbors ) in self.rotation.iter() {
+            for w in neighbors.iter() {
+                if visited.contains( &( v, w ) ) {
+                    continue;
+                }
+                let mut face = Vec::new();
+                let ( mut a, mut b ) = ( v, w );
+                while visited.insert( ( a, b ) ) {
+                    face.push( a.clone() );
+                    let around = &self.rotation[ b ];
+                    let next = &around[ ( position[ &( b, a ) ] + around.len() - 1 ) % around.len() ];
+                    ( a, b ) = ( b, next );
+                }
+                faces.push( face );
+            }
+        }
+        faces
+    }
+}
+
+#[derive( Debug, Clone, Copy, PartialEq, Eq )]
+pub enum KuratowskiKind {
+    K5,
+    K33
+}
+
+// Subdivision of K5 or K3,3 inside a graph that is not planar, edges are stored smaller end first.
+#[derive( Error, Debug, Clone, PartialEq, Eq )]
+#[error( "Graph Error: Not Planar" )]
+pub struct Kuratowski<I>( pub BTreeSet<( I, I )> );
+
+impl<I> Kuratowski<I>
+where
+    I: Clone + Ord
+{
+    fn degrees( &self ) -> BTreeMap<&I, usize> {
+        let mut degrees = BTreeMap::new();
+        for ( u, v ) in self.0.iter() {
+            *degrees.entry( u ).or_insert( 0 ) += 1;
+            *degrees.entry( v ).or_insert( 0 ) += 1;
+        }
+        degrees
+    }
+
+    pub fn nodes( &self ) -> BTreeSet<I> {
+        self.degrees().into_keys().cloned().collect()
+    }
+
+    // The nodes of the underlying K5 or K3,3, every other node only subdivides an edge.
+    pub fn branch_nodes( &self ) -> BTreeSet<I> {
+        self.degrees().into_iter().filter( |( _, degree )| *degree > 2 ).map( |( id, _ )| id.clone() ).collect()
+    }
+
+    pub fn kind( &self ) -> KuratowskiKind {
+        if self.branch_nodes().len() == 5 { KuratowskiKind::K5 } else { KuratowskiKind::K33 }
+    }
+}
+
+// Return edges of one side, `low` and `high` are the lowest and highest by lowpoint.
+#[derive( Clone, Copy, Default )]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>
+}
+
+impl Interval {
+    fn is_empty( &self ) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+#[derive( Clone, Copy, Default )]
+struct ConflictPair {
+    left: Interval,
+    right: Interval
+}
+
+impl ConflictPair {
+    fn swap( &mut self ) {
+        std::mem::swap( &mut self.left, &mut self.right );
+    }
+}
+
+// Doubly linked clockwise lists of half edges around every node, built up while the embedding is
+// placed. Half edge `2 * e` leaves the source of edge `e` and `2 * e + 1` leaves its target.
+struct Rotation {
+    head: Vec<usize>,
+    cw: Vec<usize>,
+    ccw: Vec<usize>,
+    first: Vec<Option<usize>>
+}
+
+impl Rotation {
+    fn new( n: usize, source: &[usize], target: &[usize] ) -> Self {
+        Self {
+            head: source.iter().zip( target.iter() ).flat_map( |( &v, &w )| [ w, v ] ).collect(),
+            cw: vec![ usize::MAX; 2 * source.len() ],
+            ccw: vec![ usize::MAX; 2 * source.len() ],
+            first: vec![ None; n ]
+        }
+    }
+
+    // Places `h` right after `reference` going clockwise around `v`.
+    fn insert_cw( &mut self, v: usize, h: usize, reference: Option<usize> ) {
+        let Some( reference ) = reference else {
+            self.cw[ h ] = h;
+            self.ccw[ h ] = h;
+            self.first[ v ] = Some( h );
+            return;
+        };
+        let next = self.cw[ reference ];
+        self.cw[ reference ] = h;
+        self.cw[ h ] = next;
+        self.ccw[ next ] = h;
+        self.ccw[ h ] = reference;
+    }
+
+    // Places `h` right before `reference` going clockwise around `v`.
+    fn insert_ccw( &mut self, v: usize, h: usize, reference: Option<usize> ) {
+        let Some( reference ) = reference else {
+            return self.insert_cw( v, h, None );
+        };
+        self.insert_cw( v, h, Some( self.ccw[ reference ] ) );
+        if self.first[ v ] == Some( reference ) {
+            self.first[ v ] = Some( h );
+        }
+    }
+
+    fn insert_first( &mut self, v: usize, h: usize ) {
+        self.insert_ccw( v, h, self.first[ v ] );
+    }
+
+    fn lists( &self ) -> Vec<Vec<usize>> {
+        self.first.iter().map( |first| {
+            let mut list = Vec::new();
+            if let Some( first ) = *first {
+                let mut h = first;
+                loop {
+                    list.push( self.head[ h ] );
+                    h = self.cw[ h ];
+                    if h == first {
+                        break;
+                    }
+                }
+            }
+            list
+        } ).collect()
+    }
+}
+
+// Brandes' formulation of the left-right planarity test by de Fraysseix and Rosenstiehl. A DFS orients
+// every edge, tree edges down and back edges up, and the test then has to put every back edge on the
+// left or the right of the tree path it returns to. Sides that must differ are collected as conflict
+// pairs on a stack, the graph is planar when they never clash. Edges are identified by their index in
+// `source` and `target`.
+struct LeftRight<'a> {
+    adjacency: &'a [Vec<( usize, usize )>],
+    source: Vec<usize>,
+    target: Vec<usize>,
+    out: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    height: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting: Vec<isize>,
+    lowpt_edge: Vec<usize>,
+    reference: Vec<Option<usize>>,
+    side: Vec<isize>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>
+}
+
+impl<'a> LeftRight<'a> {
+    fn new( adjacency: &'a [Vec<( usize, usize )>], m: usize ) -> Self {
+        let n = adjacency.len();
+        Self {
+            adjacency,
+            source: vec![ usize::MAX; m ],
+            target: vec![ usize::MAX; m ],
+            out: vec![ Vec::new(); n ],
+            roots: Vec::new(),
+            height: vec![ usize::MAX; n ],
+            parent: vec![ None; n ],
+            lowpt: vec![ 0; m ],
+            lowpt2: vec![ 0; m ],
+            nesting: vec![ 0; m ],
+            lowpt_edge: vec![ usize::MAX; m ],
+            reference: vec![ None; m ],
+            side: vec![ 1; m ],
+            stack: Vec::new(),
+            stack_bottom: vec![ 0; m ]
+        }
+    }
+
+    // Nesting depth of an oriented edge and its contribution to the lowpoints of the parent edge.
+    fn finish_orientation( &mut self, vw: usize ) {
+        let v = self.source[ vw ];
+        self.nesting[ vw ] = 2 * self.lowpt[ vw ] as isize + isize::from( self.lowpt2[ vw ] < self.height[ v ] );
+        let Some( e ) = self.parent[ v ] else {
+            return;
+        };
+        if self.lowpt[ vw ] < self.lowpt[ e ] {
+            self.lowpt2[ e ] = self.lowpt[ e ].min( self.lowpt2[ vw ] );
+            self.lowpt[ e ] = self.lowpt[ vw ];
+        } else if self.lowpt[ vw ] > self.lowpt[ e ] {
+            self.lowpt2[ e ] = self.lowpt2[ e ].min( self.lowpt[ vw ] );
+        } else {
+            self.lowpt2[ e ] = self.lowpt2[ e ].min( self.lowpt2[ vw ] );
+        }
+    }
+
+    fn orient( &mut self ) {
+        let mut oriented = vec![ false; self.source.len() ];
+        for root in 0..self.adjacency.len() {
+            if self.height[ root ] != usize::MAX {
+                continue;
+            }
+            self.height[ root ] = 0;
+            self.roots.push( root );
+            let mut frames = vec![ ( root, 0 ) ];
+            while let Some( &( v, i ) ) = frames.last() {
+                if let Some( &( w, vw ) ) = self.adjacency[ v ].get( i ) {
+                    if !oriented[ vw ] {
+                        oriented[ vw ] = true;
+                        self.source[ vw ] = v;
+                        self.target[ vw ] = w;
+                        self.out[ v ].push( vw );
+                        self.lowpt[ vw ] = self.height[ v ];
+                        self.lowpt2[ vw ] = self.height[ v ];
+                        if self.height[ w ] == usize::MAX {
+                            self.parent[ w ] = Some( vw );
+                            self.height[ w ] = self.height[ v ] + 1;
+                            frames.push( ( w, 0 ) );
+                            continue;
+                        }
+                        self.lowpt[ vw ] = self.height[ w ];
+                        self.finish_orientation( vw );
+                    }
+                } else {
+                    frames.pop();
+                    let Some( e ) = self.parent[ v ] else {
+                        continue;
+                    };
+                    self.finish_orientation( e );
+                }
+                if let Some( frame ) = frames.last_mut() {
+                    frame.1 += 1;
+                }
+            }
+        }
+        self.sort_out();
+    }
+
+    // Bucket sort by nesting depth, which lies within -2n - 1..=2n + 1 once signed. Edges with equal
+    // depth keep their order.
+    fn sort_out( &mut self ) {
+        let offset = 2 * self.adjacency.len() + 1;
+        let mut buckets = vec![ Vec::new(); 2 * offset + 1 ];
+        for edges in self.out.iter_mut() {
+            for e in edges.drain( .. ) {
+                buckets[ self.nesting[ e ].wrapping_add_unsigned( offset ) as usize ].push( e );
+            }
+        }
+        for e in buckets.into_iter().flatten() {
+            self.out[ self.source[ e ] ].push( e );
+        }
+    }
+
+    fn conflicting( &self, interval: Interval, edge: usize ) -> bool {
+        interval.high.is_some_and( |high| self.lowpt[ high ] > self.lowpt[ edge ] )
+    }
+
+    fn lowest( &self, pair: &ConflictPair ) -> Option<usize> {
+        [ pair.left.low, pair.right.low ].into_iter().flatten().map( |e| self.lowpt[ e ] ).min()
+    }
+
+    fn set_reference( &mut self, edge: Option<usize>, to: Option<usize> ) {
+        if let Some( edge ) = edge {
+            self.reference[ edge ] = to;
+        }
+    }
+
+    // Merges the return edges of `ei` with those of its earlier siblings, false when they clash.
+    fn add_constraints( &mut self, ei: usize, e: usize ) -> bool {
+        let mut pair = ConflictPair::default();
+        while self.stack.len() > self.stack_bottom[ ei ] {
+            let Some( mut q ) = self.stack.pop() else {
+                break;
+            };
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+            if q.right.low.is_some_and( |low| self.lowpt[ low ] > self.lowpt[ e ] ) {
+                if pair.right.is_empty() {
+                    pair.right = q.right;
+                } else {
+                    self.set_reference( pair.right.low, q.right.high );
+                }
+                pair.right.low = q.right.low;
+            } else {
+                self.set_reference( q.right.low, Some( self.lowpt_edge[ e ] ) );
+            }
+        }
+        while self.stack.last().is_some_and( |top| self.conflicting( top.left, ei ) || self.conflicting( top.right, ei ) ) {
+            let Some( mut q ) = self.stack.pop() else {
+                break;
+            };
+            if self.conflicting( q.right, ei ) {
+                q.swap();
+            }
+            if self.conflicting( q.right, ei ) {
+                return false;
+            }
+            self.set_reference( pair.right.low, q.right.high );
+            if q.right.low.is_some() {
+                pair.right.low = q.right.low;
+            }
+            if pair.left.is_empty() {
+                pair.left = q.left;
+            } else {
+                self.set_reference( pair.left.low, q.left.high );
+            }
+            pair.left.low = q.left.low;
+        }
+        if !( pair.left.is_empty() && pair.right.is_empty() ) {
+            self.stack.push( pair );
+        }
+        true
+    }
+
+    // Drops the back edges that end at the parent `u` of the tree edge `e`.
+    fn remove_back_edges( &mut self, e: usize ) {
+        let u = self.source[ e ];
+        while self.stack.last().is_some_and( |top| self.lowest( top ) == Some( self.height[ u ] ) ) {
+            if let Some( low ) = self.stack.pop().and_then( |pair| pair.left.low ) {
+                self.side[ low ] = -1;
+            }
+        }
+        if let Some( mut pair ) = self.stack.pop() {
+            while let Some( high ) = pair.left.high.filter( |&high| self.target[ high ] == u ) {
+                pair.left.high = self.reference[ high ];
+            }
+            if let Some( low ) = pair.left.low.filter( |_| pair.left.high.is_none() ) {
+                self.reference[ low ] = pair.right.low;
+                self.side[ low ] = -1;
+                pair.left.low = None;
+            }
+            while let Some( high ) = pair.right.high.filter( |&high| self.target[ high ] == u ) {
+                pair.right.high = self.reference[ high ];
+            }
+            if let Some( low ) = pair.right.low.filter( |_| pair.right.high.is_none() ) {
+                self.reference[ low ] = pair.left.low;
+                self.side[ low ] = -1;
+                pair.right.low = None;
+            }
+            self.stack.push( pair );
+        }
+        // The side of `e` follows a highest return edge.
+        if self.lowpt[ e ] < self.height[ u ] {
+            if let Some( top ) = self.stack.last() {
+                self.reference[ e ] = match ( top.left.high, top.right.high ) {
+                    ( Some( left ), Some( right ) ) if self.lowpt[ left ] > self.lowpt[ right ] => Some( left ),
+                    ( Some( left ), None ) => Some( left ),
+                    ( _, right ) => right
+                };
+            }
+        }
+    }
+
+    // Return edges of the `i`th outgoing edge `ei` of `v` join those of the parent edge.
+    fn integrate( &mut self, v: usize, i: usize, ei: usize ) -> bool {
+        if self.lowpt[ ei ] >= self.height[ v ] {
+            return true;
+        }
+        match self.parent[ v ] {
+            Some( e ) if i == 0 => {
+                self.lowpt_edge[ e ] = self.lowpt_edge[ ei ];
+                true
+            }
+            Some( e ) => self.add_constraints( ei, e ),
+            None => true
+        }
+    }
+
+    fn test( &mut self ) -> bool {
+        for root in self.roots.clone() {
+            let mut frames = vec![ ( root, 0 ) ];
+            while let Some( &( v, i ) ) = frames.last() {
+                if let Some( &ei ) = self.out[ v ].get( i ) {
+                    let w = self.target[ ei ];
+                    self.stack_bottom[ ei ] = self.stack.len();
+                    if self.parent[ w ] == Some( ei ) {
+                        frames.push( ( w, 0 ) );
+                        continue;
+                    }
+                    self.lowpt_edge[ ei ] = ei;
+                    self.stack.push( ConflictPair { left: Interval::default(), right: Interval { low: Some( ei ), high: Some( ei ) } } );
+                    if !self.integrate( v, i, ei ) {
+                        return false;
+                    }
+                } else {
+                    frames.pop();
+                    let Some( e ) = self.parent[ v ] else {
+                        continue;
+                    };
+                    self.remove_back_edges( e );
+                    let Some( &( u, j ) ) = frames.last() else {
+                        continue;
+                    };
+                    if !self.integrate( u, j, e ) {
+                        return false;
+                    }
+                }
+                if let Some( frame ) = frames.last_mut() {
+                    frame.1 += 1;
+                }
+            }
+        }
+        true
+    }
+
+    // Resolves the chain of references below `e`, every edge takes the side relative to the next.
+    fn sign( &mut self, e: usize ) -> isize {
+        let mut chain = vec![ e ];
+        while let Some( next ) = chain.last().and_then( |&last| self.reference[ last ] ) {
+            chain.push( next );
+        }
+        for k in ( 0..chain.len() - 1 ).rev() {
+            self.side[ chain[ k ] ] *= self.side[ chain[ k + 1 ] ];
+            self.reference[ chain[ k ] ] = None;
+        }
+        self.side[ e ]
+    }
+
+    // Clockwise neighbours of every node, only valid after a successful test.
+    fn embed( mut self ) -> Vec<Vec<usize>> {
+        for e in 0..self.nesting.len() {
+            self.nesting[ e ] *= self.sign( e );
+        }
+        self.sort_out();
+
+        let n = self.adjacency.len();
+        let mut rotation = Rotation::new( n, &self.source, &self.target );
+        for ( v, edges ) in self.out.iter().enumerate() {
+            let mut previous = None;
+            for &e in edges.iter() {
+                rotation.insert_cw( v, 2 * e, previous );
+                previous = Some( 2 * e );
+            }
+        }
+
+        // Half edges at every node that the next back edges are placed against.
+        let mut left = vec![ 0; n ];
+        let mut right = vec![ 0; n ];
+        for &root in self.roots.iter() {
+            let mut frames = vec![ ( root, 0 ) ];
+            while let Some( frame ) = frames.last_mut() {
+                let v = frame.0;
+                let Some( &ei ) = self.out[ v ].get( frame.1 ) else {
+                    frames.pop();
+                    continue;
+                };
+                frame.1 += 1;
+                let w = self.target[ ei ];
+                if self.parent[ w ] == Some( ei ) {
+                    rotation.insert_first( w, 2 * ei + 1 );
+                    left[ v ] = 2 * ei;
+                    right[ v ] = 2 * ei;
+                    frames.push( ( w, 0 ) );
+                } else if self.side[ ei ] == 1 {
+                    rotation.insert_cw( w, 2 * ei + 1, Some( right[ w ] ) );
+                } else {
+                    rotation.insert_ccw( w, 2 * ei + 1, Some( left[ w ] ) );
+                    left[ w ] = 2 * ei + 1;
+                }
+            }
+        }
+        rotation.lists()
+    }
+}
+
+// Numbers the edges of a simple adjacency, every neighbour comes with the index of its edge. An edge
+// is numbered at its smaller end and looked up at the larger one.
+fn number_edges( adjacency: &[Vec<usize>] ) -> ( Vec<Vec<( usize, usize )>>, usize ) {
+    let n = adjacency.len();
+    let mut lower: Vec<Vec<( usize, usize )>> = vec![ Vec::new(); n ];
+    let mut id = vec![ usize::MAX; n ];
+    let mut m = 0;
+    let mut numbered = Vec::with_capacity( n );
+    for ( v, neighbors ) in adjacency.iter().enumerate() {
+        for ( u, e ) in std::mem::take( &mut lower[ v ] ) {
+            id[ u ] = e;
+        }
+        numbered.push( neighbors.iter().map( |&w| {
+            if w < v {
+                return ( w, id[ w ] );
+            }
+            lower[ w ].push( ( v, m ) );
+            m += 1;
+            ( w, m - 1 )
+        } ).collect() );
+    }
+    ( numbered, m )
+}
+
+// Clockwise neighbours of every node, None when the graph is not planar.
+fn planar_embedding( adjacency: &[Vec<usize>] ) -> Option<Vec<Vec<usize>>> {
+    let n = adjacency.len();
+    let ( numbered, m ) = number_edges( adjacency );
+    if n > 2 && m > 3 * n - 6 {
+        return None;
+    }
+    let mut left_right = LeftRight::new( &numbered, m );
+    left_right.orient();
+    left_right.test().then( || left_right.embed() )
+}
+
+fn is_planar( adjacency: &[Vec<usize>] ) -> bool {
+    let n = adjacency.len();
+    let ( numbered, m ) = number_edges( adjacency );
+    if n > 2 && m > 3 * n - 6 {
+        return false;
+    }
+    let mut left_right = LeftRight::new( &numbered, m );
+    left_right.orient();
+    left_right.test()
+}
+
+// Adjacency of `edges` on their own, nodes are numbered in order of appearance. `label` has to be
+// all `usize::MAX` and is left that way.
+fn compact( edges: &[( usize, usize )], label: &mut [usize] ) -> Vec<Vec<usize>> {
+    let mut adjacency: Vec<Vec<usize>> = Vec::new();
+    for &( v, w ) in edges.iter() {
+        for x in [ v, w ] {
+            if label[ x ] == usize::MAX {
+                label[ x ] = adjacency.len();
+                adjacency.push( Vec::new() );
+            }
+        }
+        adjacency[ label[ v ] ].push( label[ w ] );
+        adjacency[ label[ w ] ].push( label[ v ] );
+    }
+    for &( v, w ) in edges.iter() {
+        label[ v ] = usize::MAX;
+        label[ w ] = usize::MAX;
+    }
+    adjacency
+}
+
+// Cuts the `alive` edges into maximal paths whose inner nodes have degree two, once edges at nodes of
+// degree one are peeled off. A Kuratowski subgraph never touches a peeled edge and takes every path
+// whole or not at all.
+fn chains( n: usize, edges: &[( usize, usize )], alive: &[usize] ) -> Vec<Vec<usize>> {
+    let mut incident = vec![ Vec::new(); n ];
+    for &e in alive.iter() {
+        incident[ edges[ e ].0 ].push( e );
+        incident[ edges[ e ].1 ].push( e );
+    }
+    let mut degree: Vec<usize> = incident.iter().map( Vec::len ).collect();
+    let mut used = vec![ true; edges.len() ];
+    alive.iter().for_each( |&e| used[ e ] = false );
+    let mut leaves: Vec<usize> = ( 0..n ).filter( |&v| degree[ v ] == 1 ).collect();
+    while let Some( v ) = leaves.pop() {
+        let Some( &e ) = incident[ v ].iter().find( |&&e| !used[ e ] ).filter( |_| degree[ v ] == 1 ) else {
+            continue;
+        };
+        used[ e ] = true;
+        let ( a, b ) = edges[ e ];
+        degree[ a ] -= 1;
+        degree[ b ] -= 1;
+        if degree[ a + b - v ] == 1 {
+            leaves.push( a + b - v );
+        }
+    }
+
+    // Paths start at nodes of any other degree, whatever is left lies on cycles of degree two nodes.
+    let starts = ( 0..n ).filter( |&v| degree[ v ] != 2 ).chain( 0..n );
+    let mut paths = Vec::new();
+    for start in starts {
+        while let Some( &first ) = incident[ start ].iter().find( |&&e| !used[ e ] ) {
+            let mut path = Vec::new();
+            let ( mut v, mut e ) = ( start, first );
+            loop {
+                used[ e ] = true;
+                path.push( e );
+                v = edges[ e ].0 + edges[ e ].1 - v;
+                let next = incident[ v ].iter().find( |&&next| !used[ next ] );
+                match next {
+                    Some( &next ) if degree[ v ] == 2 => e = next,
+                    _ => break
+                }
+            }
+            paths.push( path );
+        }
+    }
+    paths
+}
+
+// Shrinks a graph that is not planar to a subdivision of K5 or K3,3, inside a non planar biconnected
+// component. Every pass spreads the chains that are not known to be needed out over the component
+// and drops them in batches for as long as the rest stays non planar. Batches halve from pass to pass,
+// so most edges go early in a few large batches and the later, smaller tests run on what is left. A
+// chain that cannot be dropped on its own is needed.
+fn kuratowski( adjacency: &[Vec<usize>] ) -> Vec<( usize, usize )> {
+    let mut label = vec![ usize::MAX; adjacency.len() ];
+    let block = biconnected( adjacency ).into_iter()
+        .find( |block| !is_planar( &compact( block, &mut label ) ) )
+        .expect( "a graph that is not planar has a biconnected component that is not planar" );
+
+    // Numbered within the block from here on, `nodes` maps back.
+    let mut nodes = Vec::new();
+    let mut number = |v: usize| {
+        if label[ v ] == usize::MAX {
+            label[ v ] = nodes.len();
+            nodes.push( v );
+        }
+        label[ v ]
+    };
+    let edges: Vec<( usize, usize )> = block.iter().map( |&( v, w )| ( number( v ), number( w ) ) ).collect();
+    let n = nodes.len();
+    let mut label = vec![ usize::MAX; n ];
+    let mut needed = vec![ false; edges.len() ];
+    let mut alive: Vec<usize> = ( 0..edges.len() ).collect();
+    let mut batch = edges.len();
+    loop {
+        let ( known, open ): ( Vec<Vec<usize>>, Vec<Vec<usize>> ) = chains( n, &edges, &alive ).into_iter()
+            .partition( |chain| chain.iter().any( |&e| needed[ e ] ) );
+        let known: Vec<usize> = known.into_iter().flatten().collect();
+        known.iter().for_each( |&e| needed[ e ] = true );
+        if open.is_empty() {
+            alive = known;
+            break;
+        }
+        // Bit reversed positions scatter neighbouring chains over different batches.
+        let mut order: Vec<usize> = ( 0..open.len() ).collect();
+        order.sort_by_key( |&i| i.reverse_bits() );
+        let open: Vec<&Vec<usize>> = order.into_iter().map( |i| &open[ i ] ).collect();
+        batch = batch.min( open.len() ).div_ceil( 2 ).max( 1 );
+
+        let mut kept: Vec<usize> = Vec::new();
+        for ( start, chunk ) in open.chunks( batch ).enumerate().map( |( i, chunk )| ( i * batch, chunk ) ) {
+            let rest = open[ start + chunk.len().. ].iter().copied().flatten();
+            let subset: Vec<( usize, usize )> = known.iter().chain( kept.iter() ).chain( rest ).map( |&e| edges[ e ] ).collect();
+            if is_planar( &compact( &subset, &mut label ) ) {
+                let chunk = chunk.iter().copied().flatten();
+                if batch == 1 {
+                    chunk.clone().for_each( |&e| needed[ e ] = true );
+                }
+                kept.extend( chunk );
+            }
+        }
+        alive = known.into_iter().chain( kept ).collect();
+    }
+    alive.into_iter()
+        .map( |e| ( nodes[ edges[ e ].0 ], nodes[ edges[ e ].1 ] ) )
+        .map( |( v, w )| ( v.min( w ), v.max( w ) ) )
+        .collect()
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Left-right test in linear time, self loops never matter.
+    pub fn is_planar( &self ) -> bool {
+        is_planar( &simple( &Indexed::new( self.data() ) ) )
+    }
+
+    // Combinatorial embedding when the graph is planar, otherwise a Kuratowski subgraph as witness.
+    // The test and the embedding take linear time. The witness repeats the test O( k log m ) times for
+    // a witness of k chains, but the first tests drop most of the graph and the rest run on what is
+    // left, so it usually costs a small multiple of one test.
+    pub fn planar_embedding( &self ) -> Result<Embedding<I>, Kuratowski<I>> {
+        let indexed = Indexed::new( self.data() );
+        let adjacency = simple( &indexed );
+        match planar_embedding( &adjacency ) {
+            Some( lists ) => Ok( Embedding {
+                rotation: lists.into_iter().enumerate()
+                    .map( |( v, list )| ( indexed.ids[ v ].clone(), list.into_iter().map( |w| indexed.ids[ w ].clone() ).collect() ) )
+                    .collect()
+            } ),
+            None => Err( Kuratowski(
+                kuratowski( &adjacency ).into_iter()
+                    .map( |( v, w )| ( indexed.ids[ v ].clone(), indexed.ids[ w ].clone() ) )
+                    .collect()
+            ) )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{ BTreeMap, BTreeSet };
+
+    use super::KuratowskiKind;
+    use crate::{
+        graph::{
+            GraphTraits,
+            fixtures::{ petersen, ungraph }
+        },
+        undirected_graph::UnGraph
+    };
+
+    fn complete( n: usize ) -> UnGraph<usize, (), ()> {
+        let edges: Vec<( usize, usize )> = ( 0..n ).flat_map( |u| ( ( u + 1 )..n ).map( move |v| ( u, v ) ) ).collect();
+        ungraph( n, &edges )
+    }
+
+    // Euler's formula for a connected plane graph, n - m + f = 2.
+    fn assert_embedding( graph: &UnGraph<usize, (), ()> ) {
+        let embedding = graph.planar_embedding().unwrap();
+        let m = embedding.rotation().values().map( Vec::len ).sum::<usize>() / 2;
+        assert_eq!( graph.order() + embedding.faces().len(), m + 2 );
+        for ( id, neighbors ) in embedding.rotation().iter() {
+            for other in neighbors.iter() {
+                assert!( graph.contains_edge( *id, *other ) );
+            }
+        }
+    }
+
+    #[test]
+    fn test_planar() {
+        assert!( complete( 4 ).is_planar() );
+        assert_embedding( &complete( 4 ) );
+
+        // Cube, octahedron with a self loop and a wheel.
+        let cube = ungraph( 8, &[ ( 0, 1 ), ( 1, 2 ), ( 2, 3 ), ( 3, 0 ), ( 4, 5 ), ( 5, 6 ), ( 6, 7 ), ( 7, 4 ), ( 0, 4 ), ( 1, 5 ), ( 2, 6 ), ( 3, 7 ) ] );
+        assert_embedding( &cube );
+        let octahedron = ungraph( 6, &[ ( 0, 1 ), ( 0, 2 ), ( 0, 3 ), ( 0, 4 ), ( 5, 1 ), ( 5, 2 ), ( 5, 3 ), ( 5, 4 ), ( 1, 2 ), ( 2, 3 ), ( 3, 4 ), ( 4, 1 ), ( 2, 2 ) ] );
+        assert!( octahedron.is_planar() );
+        assert_embedding( &octahedron );
+        let wheel: Vec<( usize, usize )> = ( 1..=8 ).flat_map( |v| [ ( 0, v ), ( v, v % 8 + 1 ) ] ).collect();
+        assert_embedding( &ungraph( 9, &wheel ) );
+
+        // A triangulated grid, maximal planar.
+        let mut grid = Vec::new();
+        for row in 0..6 {
+            for column in 0..6 {
+                let v = row * 6 + column;
+                if column < 5 {
+                    grid.push( ( v, v + 1 ) );
+                }
+                if row < 5 {
+                    grid.push( ( v, v + 6 ) );
+                }
+                if column < 5 && row < 5 {
+                    grid.push( ( v, v + 7 ) );
+                }
+            }
+        }
+        assert_embedding( &ungraph( 36, &grid ) );
+
+        let forest = ungraph( 5, &[ ( 0, 1 ), ( 2, 3 ) ] );
+        let embedding = forest.planar_embedding().unwrap();
+        assert_eq!( embedding.neighbors( &0 ), Some( &[ 1 ][ .. ] ) );
+        assert_eq!( embedding.neighbors( &4 ), Some( &[][ .. ] ) );
+        assert_eq!( embedding.faces().len(), 2 );
+    }
+
+    #[test]
+    fn test_kuratowski() {
+        let witness = complete( 5 ).planar_embedding().unwrap_err();
+        assert_eq!( witness.kind(), KuratowskiKind::K5 );
+        assert_eq!( witness.0.len(), 10 );
+
+        // Petersen graph, its witness is a subdivided K3,3.
+        let petersen = petersen();
+        assert!( !petersen.is_planar() );
+        let witness = petersen.planar_embedding().unwrap_err();
+        assert_eq!( witness.kind(), KuratowskiKind::K33 );
+        assert_eq!( witness.branch_nodes().len(), 6 );
+        for ( u, v ) in witness.0.iter() {
+            assert!( u < v && petersen.contains_edge( *u, *v ) );
+        }
+
+        // K3,3 with an extra planar part hanging off.
+        let mut edges: Vec<( usize, usize )> = ( 0..3 ).flat_map( |u| ( 3..6 ).map( move |v| ( u, v ) ) ).collect();
+        edges.extend( [ ( 5, 6 ), ( 6, 7 ), ( 7, 5 ) ] );
+        let witness = ungraph( 8, &edges ).planar_embedding().unwrap_err();
+        assert_eq!( witness.nodes(), ( 0..6 ).collect::<BTreeSet<usize>>() );
+        assert_eq!( witness.kind(), KuratowskiKind::K33 );
+    }
+
+    #[test]
+    fn test_kuratowski_large() {
+        // A triangulated 40 x 40 grid, planar until both diagonals of the outer face are added.
+        let size = 40;
+        let mut edges = Vec::new();
+        for row in 0..size {
+            for column in 0..size {
+                let v = row * size + column;
+                if column + 1 < size {
+                    edges.push( ( v, v + 1 ) );
+                }
+                if row + 1 < size {
+                    edges.push( ( v, v + size ) );
+                }
+                if column + 1 < size && row + 1 < size {
+                    edges.push( ( v, v + size + 1 ) );
+                }
+            }
+        }
+        edges.push( ( 0, size * size - 1 ) );
+        assert_embedding( &ungraph( size * size, &edges ) );
+        edges.push( ( size - 1, size * ( size - 1 ) ) );
+        let graph = ungraph( size * size, &edges );
+        assert!( !graph.is_planar() );
+
+        // Branch nodes of degree four in K5 or three in K3,3, every other node subdivides a path.
+        let witness = graph.planar_embedding().unwrap_err();
+        let mut degrees = BTreeMap::new();
+        for ( u, v ) in witness.0.iter() {
+            assert!( graph.contains_edge( *u, *v ) );
+            *degrees.entry( *u ).or_insert( 0 ) += 1;
+            *degrees.entry( *v ).or_insert( 0 ) += 1;
+        }
+        let branch = match witness.kind() {
+            KuratowskiKind::K5 => 4,
+            KuratowskiKind::K33 => 3
+        };
+        assert!( degrees.values().all( |&degree| degree == 2 || degree == branch ) );
+        assert_eq!( witness.branch_nodes().len(), if branch == 4 { 5 } else { 6 } );
+        assert!( !ungraph( size * size, &witness.0.into_iter().collect::<Vec<_>>() ).is_planar() );
+    }
+}
+
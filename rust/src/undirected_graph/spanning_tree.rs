@@ -0,0 +1,258 @@
+// Copyright 2024 Bewusstsein Labs
+
+//: Standard
+use std::{
+    cmp::{ Ordering, Reverse },
+    collections::BinaryHeap
+};
+
+use crate::{
+    graph::{
+        GraphAccess,
+        GraphTraits,
+        indexed::Indexed,
+        weight::Weight
+    },
+    undirected_graph::UnGraph
+};
+
+pub(crate) struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>
+}
+
+impl DisjointSet {
+    pub fn new( n: usize ) -> Self {
+        Self {
+            parent: ( 0..n ).collect(),
+            rank: vec![ 0; n ]
+        }
+    }
+
+    pub fn find( &mut self, mut x: usize ) -> usize {
+        while self.parent[ x ] != x {
+            self.parent[ x ] = self.parent[ self.parent[ x ] ];
+            x = self.parent[ x ];
+        }
+        x
+    }
+
+    pub fn union( &mut self, a: usize, b: usize ) -> bool {
+        let ( a, b ) = ( self.find( a ), self.find( b ) );
+        if a == b {
+            return false;
+        }
+        match self.rank[ a ].cmp( &self.rank[ b ] ) {
+            Ordering::Less => self.parent[ a ] = b,
+            Ordering::Greater => self.parent[ b ] = a,
+            Ordering::Equal => {
+                self.parent[ b ] = a;
+                self.rank[ a ] += 1;
+            }
+        }
+        true
+    }
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord
+{
+    // Every undirected edge once, as ( u, v, edge ) with u < v.
+    fn undirected_edges<'g>( indexed: &Indexed<'g, I, E> ) -> Vec<( usize, usize, &'g E )> {
+        indexed.adjacency.iter()
+            .enumerate()
+            .flat_map( |( u, edges )| edges.iter().filter( move |( v, _ )| u < *v ).map( move |&( v, edge )| ( u, v, edge ) ) )
+            .collect()
+    }
+
+    fn kruskal_keyed<K, F>( &self, key: F ) -> Vec<( I, I )>
+    where
+        K: Ord,
+        F: Fn( &E ) -> K
+    {
+        let indexed = Indexed::new( self.data() );
+        let mut edges: Vec<( K, usize, usize )> = Self::undirected_edges( &indexed ).into_iter()
+            .map( |( u, v, edge )| ( key( edge ), u, v ) )
+            .collect();
+        edges.sort_by( |a, b| a.0.cmp( &b.0 ) );
+
+        let mut sets = DisjointSet::new( indexed.ids.len() );
+        edges.into_iter()
+            .filter( |&( _, u, v )| sets.union( u, v ) )
+            .map( |( _, u, v )| ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() ) )
+            .collect()
+    }
+
+    fn prim_keyed<K, F>( &self, key: F ) -> Vec<( I, I )>
+    where
+        K: Ord,
+        F: Fn( &E ) -> K
+    {
+        let indexed = Indexed::new( self.data() );
+        let n = indexed.ids.len();
+        let mut in_tree = vec![ false; n ];
+        let mut tree = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        for root in 0..n {
+            if in_tree[ root ] {
+                continue;
+            }
+            in_tree[ root ] = true;
+            heap.extend( indexed.adjacency[ root ].iter().map( |&( v, edge )| Reverse( ( key( edge ), root, v ) ) ) );
+            while let Some( Reverse( ( _, u, v ) ) ) = heap.pop() {
+                if in_tree[ v ] {
+                    continue;
+                }
+                in_tree[ v ] = true;
+                tree.push( ( indexed.ids[ u ].clone(), indexed.ids[ v ].clone() ) );
+                heap.extend( indexed.adjacency[ v ].iter()
+                    .filter( |&&( w, _ )| !in_tree[ w ] )
+                    .map( |&( w, edge )| Reverse( ( key( edge ), v, w ) ) )
+                );
+            }
+        }
+        tree
+    }
+
+    fn total_weight<W, F>( &self, edges: &[( I, I )], weight: F ) -> W
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        edges.iter()
+            .filter_map( |( u, v )| self.data().get_edge( u.clone(), v.clone() ) )
+            .fold( W::zero(), |total, edge| total + weight( edge ) )
+    }
+
+    // Spanning forest edges, one tree per connected component.
+    pub fn kruskal<W, F>( &self, weight: F ) -> ( Vec<( I, I )>, W )
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let edges = self.kruskal_keyed( &weight );
+        let total = self.total_weight( &edges, weight );
+        ( edges, total )
+    }
+
+    pub fn prim<W, F>( &self, weight: F ) -> ( Vec<( I, I )>, W )
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let edges = self.prim_keyed( &weight );
+        let total = self.total_weight( &edges, weight );
+        ( edges, total )
+    }
+}
+
+impl<I, N, E> UnGraph<I, N, E>
+where
+    I: Clone + Ord,
+    N: Clone + PartialEq,
+    E: Clone + PartialEq
+{
+    fn spanning_graph( &self, edges: &[( I, I )] ) -> UnGraph<I, N, E> {
+        let mut graph = UnGraph::new();
+        for ( id, node ) in self.data().iter() {
+            let _ = graph.add_node( id.clone(), node.data().clone() );
+        }
+        for ( u, v ) in edges.iter() {
+            if let Some( edge ) = self.data().get_edge( u.clone(), v.clone() ) {
+                let _ = graph.add_edge( u.clone(), v.clone(), edge.clone() );
+            }
+        }
+        graph
+    }
+
+    pub fn minimum_spanning_tree<W, F>( &self, weight: F ) -> ( UnGraph<I, N, E>, W )
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let edges = self.kruskal_keyed( &weight );
+        ( self.spanning_graph( &edges ), self.total_weight( &edges, weight ) )
+    }
+
+    pub fn maximum_spanning_tree<W, F>( &self, weight: F ) -> ( UnGraph<I, N, E>, W )
+    where
+        W: Weight,
+        F: Fn( &E ) -> W
+    {
+        let edges = self.kruskal_keyed( |edge| Reverse( weight( edge ) ) );
+        ( self.spanning_graph( &edges ), self.total_weight( &edges, weight ) )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{
+            GraphTraits,
+            weight::Total
+        },
+        undirected_graph::UnGraph
+    };
+
+    fn graph() -> UnGraph<char, (), u32> {
+        let mut graph = UnGraph::<char, (), u32>::new();
+        for id in [ 'a', 'b', 'c', 'd', 'e', 'x', 'y' ] {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 'a', 'b', 4 ).unwrap();
+        graph.add_edge( 'a', 'c', 1 ).unwrap();
+        graph.add_edge( 'b', 'c', 2 ).unwrap();
+        graph.add_edge( 'b', 'd', 5 ).unwrap();
+        graph.add_edge( 'c', 'd', 8 ).unwrap();
+        graph.add_edge( 'd', 'e', 3 ).unwrap();
+        graph.add_edge( 'c', 'e', 9 ).unwrap();
+        graph.add_edge( 'x', 'y', 7 ).unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_kruskal_prim() {
+        let graph = graph();
+        let ( kruskal, kruskal_weight ) = graph.kruskal( |weight| *weight );
+        let ( prim, prim_weight ) = graph.prim( |weight| *weight );
+        assert_eq!( kruskal.len(), 5 );
+        assert_eq!( prim.len(), 5 );
+        assert_eq!( kruskal_weight, 18 );
+        assert_eq!( prim_weight, 18 );
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let ( tree, weight ) = graph().minimum_spanning_tree( |weight| *weight );
+        assert_eq!( weight, 18 );
+        assert_eq!( tree.order(), 7 );
+        assert_eq!( tree.size(), 5 );
+        assert!( tree.contains_edge( 'c', 'a' ) );
+        assert!( tree.contains_edge( 'x', 'y' ) );
+        assert!( !tree.contains_edge( 'c', 'e' ) );
+        assert!( !tree.has_cycle() );
+    }
+
+    #[test]
+    fn test_maximum_spanning_tree() {
+        let ( tree, weight ) = graph().maximum_spanning_tree( |weight| *weight );
+        assert_eq!( weight, 33 );
+        assert!( tree.contains_edge( 'c', 'e' ) );
+        assert!( !tree.contains_edge( 'a', 'c' ) );
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree_float() {
+        let mut graph = UnGraph::<usize, (), f64>::new();
+        for id in 1..=3 {
+            graph.add_node( id, () ).unwrap();
+        }
+        graph.add_edge( 1, 2, 0.5 ).unwrap();
+        graph.add_edge( 2, 3, 1.5 ).unwrap();
+        graph.add_edge( 1, 3, 0.75 ).unwrap();
+        let ( _, weight ) = graph.minimum_spanning_tree( |weight| Total( *weight ) );
+        assert_eq!( weight, Total( 1.25 ) );
+    }
+}